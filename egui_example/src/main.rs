@@ -1,5 +1,10 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::num::NonZeroU32;
+use std::rc::Rc;
+
 use egui_inspect::EguiInspect;
 
 use eframe::egui;
@@ -26,10 +31,73 @@ struct MyApp {
     ugly_internal_field_name_2: Salut,
     #[inspect(name = "A struct with three floats")]
     vector_struct: Vector,
+    #[inspect(custom_func_mut = "custom_bool_inspect")]
+    boolean: bool,
+    #[inspect(name = "A fieldless enum")]
+    direction: Direction,
+    #[inspect(name = "An enum with fields")]
+    shape: Shape,
+    #[inspect(name = "An optional value")]
+    optional: Option<u32>,
+    #[inspect(name = "An optional string", factory = "default_optional_string")]
+    optional_string: Option<String>,
+    #[inspect(name = "A map")]
+    scores: BTreeMap<String, u32>,
+    #[inspect(name = "A set")]
+    tags: BTreeSet<String>,
+    #[inspect(name = "A deque")]
+    queue: VecDeque<i32>,
+    #[inspect(name = "A shared vector")]
+    shared_vector: Rc<RefCell<Vector>>,
+    #[inspect(name = "The same shared vector")]
+    shared_vector_copy: Rc<RefCell<Vector>>,
+    #[inspect(name = "A tuple")]
+    tuple: (f32, f32),
+    #[inspect(name = "A list of tuples")]
+    named_tuples: Vec<(String, u32)>,
+    #[inspect(name = "A unit struct")]
+    marker: Marker,
+    #[inspect(name = "A character")]
+    character: char,
+    #[inspect(name = "A 128 bits integer")]
+    big_number: u128,
+    #[inspect(name = "A non zero integer")]
+    non_zero: NonZeroU32,
+    #[inspect(name = "A large counter", range = 0..=u64::MAX)]
+    large_counter: u64,
+    #[inspect(name = "A speed", speed = 0.1, decimals = 1, suffix = " m/s")]
+    speed: f32,
+    #[inspect(name = "A frequency", logarithmic, range = 20.0..=20000.0, clamp = always, suffix = " Hz")]
+    frequency: f32,
+    #[inspect(name = "A register", format = hex)]
+    register: u32,
+    #[inspect(name = "Some flags", bits = ["visible", "solid", "static"])]
+    flags: u8,
+    #[inspect(name = "A rotation", angle, range = -180..=180)]
+    rotation: f32,
+    #[inspect(name = "An opacity", percent, slider)]
+    opacity: f32,
+    #[inspect(name = "A time step", expr, suffix = " s")]
+    time_step: f64,
+    #[inspect(name = "A tint", color(linear, alpha))]
+    tint: [f32; 4],
+    background: egui::Color32,
+    area: egui::Rect,
+    outline: egui::Stroke,
+    anchor: egui::Align2,
+    position: [f32; 3],
+    #[inspect(name = "Some weights", inline)]
+    weights: Vec<f32>,
+    #[inspect(new_item = "new_spawn_point", item_label_fn = "spawn_point_label")]
+    spawn_points: Vec<egui::Pos2>,
+    samples: Vec<f32>,
+    #[inspect(name = "Some generic pairs")]
+    pairs: Vec<Pair<u8>>,
 }
 
 impl Default for MyApp {
     fn default() -> Self {
+        let shared_vector = Rc::new(RefCell::new(Vector { x: 1.0, y: 2.0, z: 3.0 }));
         Self {
             string: "I am a single line string".to_owned(),
             code: "Hello\nI\nam\na\nmultiline\nstring".to_owned(),
@@ -43,12 +111,49 @@ impl Default for MyApp {
             ugly_internal_field_name: 16,
             ugly_internal_field_name_2: Salut(50, 123.45),
             vector_struct: Vector { x: 10.0, y: 20.0, z: 30.0 },
+            boolean: false,
+            direction: Direction::North,
+            shape: Shape::Circle { radius: 5.0 },
+            optional: Some(12),
+            optional_string: None,
+            scores: BTreeMap::from([("Alice".to_owned(), 12), ("Bob".to_owned(), 42)]),
+            tags: BTreeSet::from(["enemy".to_owned(), "flying".to_owned()]),
+            queue: VecDeque::from([1, 2, 3]),
+            shared_vector: shared_vector.clone(),
+            shared_vector_copy: shared_vector,
+            tuple: (4.0, 2.0),
+            named_tuples: vec![("Anvil".to_owned(), 3), ("Hammer".to_owned(), 1)],
+            marker: Marker,
+            character: 'x',
+            big_number: u128::MAX,
+            non_zero: NonZeroU32::new(7).unwrap(),
+            large_counter: 1 << 40,
+            speed: 3.5,
+            frequency: 440.0,
+            register: 0xdead_beef,
+            flags: 0b101,
+            rotation: std::f32::consts::FRAC_PI_4,
+            opacity: 0.8,
+            time_step: 1.0 / 60.0,
+            tint: [1.0, 0.5, 0.2, 1.0],
+            background: egui::Color32::DARK_BLUE,
+            area: egui::Rect::from_min_size(egui::pos2(10.0, 20.0), egui::vec2(100.0, 50.0)),
+            outline: egui::Stroke::new(2.0, egui::Color32::WHITE),
+            anchor: egui::Align2::CENTER_CENTER,
+            position: [1.0, 2.0, 3.0],
+            weights: vec![0.2, 0.3, 0.5],
+            spawn_points: vec![egui::pos2(0.0, 0.0), egui::pos2(10.0, 5.0)],
+            samples: (0..10_000).map(|i| (i as f32 / 100.0).sin()).collect(),
+            pairs: vec![Pair { first: 1, second: 2 }],
         }
     }
 }
 
 #[derive(EguiInspect)]
-struct Salut(i32, f32);
+struct Salut(#[inspect(name = "An integer")] i32, #[inspect(slider, min = 0.0, max = 200.0)] f32);
+
+#[derive(EguiInspect)]
+struct Marker;
 
 #[derive(EguiInspect)]
 struct Vector { 
@@ -60,6 +165,43 @@ struct Vector {
     z: f32 
 }
 
+#[derive(EguiInspect)]
+enum Direction {
+    North,
+    #[inspect(name = "East (sunrise)")]
+    East,
+    South,
+    West,
+}
+
+#[derive(EguiInspect, Clone, Default)]
+#[inspect(default, clone)]
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+#[derive(EguiInspect)]
+enum Shape {
+    Circle {
+        #[inspect(slider, min = 0.0, max = 50.0)]
+        radius: f32,
+    },
+    Rectangle {
+        width: f32,
+        height: f32,
+    },
+    #[inspect(default = "default_label")]
+    Label(#[inspect(name = "Text")] String),
+}
+
+fn default_label() -> Shape {
+    Shape::Label("Some text".to_owned())
+}
+
+fn default_optional_string() -> String {
+    "Freshly enabled".to_owned()
+}
 
 fn custom_bool_inspect(boolean: &mut bool, label: &'static str, ui: &mut egui::Ui) {
     ui.label("C'EST LA GIGA FONCTION CUSTOM WÉ");
@@ -69,6 +211,11 @@ fn custom_bool_inspect(boolean: &mut bool, label: &'static str, ui: &mut egui::U
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
+            let mut expression_input = egui_inspect::expression_input(ctx);
+            if ui.checkbox(&mut expression_input, "Type math expressions in every number").changed() {
+                egui_inspect::set_expression_input(ctx, expression_input);
+            }
+
             self.inspect_mut("Test App", ui);
             // self.inspect("Test App", ui);
 
@@ -81,6 +228,14 @@ impl eframe::App for MyApp {
     }
 }
 
+fn new_spawn_point() -> egui::Pos2 {
+    egui::pos2(50.0, 50.0)
+}
+
+fn spawn_point_label(point: &egui::Pos2) -> String {
+    format!("Spawn at ({}, {})", point.x, point.y)
+}
+
 fn main() {
     let options = eframe::NativeOptions::default();
     eframe::run_native("My egui App", options, Box::new(|_cc| Box::new(MyApp::default())));
@@ -1,23 +1,290 @@
-use std::ops::Add;
-use crate::InspectNumber;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+};
+use std::cell::{Cell, RefCell};
+use std::ops::{Add, RangeInclusive};
+use std::str::FromStr;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, PoisonError, RwLock, TryLockError};
+use crate::{CollectionOptions, ColorOptions, ColorSpace, IntegerFormat, InspectNumber, NumberOptions, SliderClamping};
+use crate::InspectCollection;
+use crate::InspectInline;
 use crate::InspectString;
-use egui::{Color32, Ui};
+use egui::emath::Numeric;
+use egui::ecolor::{self, Hsva};
+use egui::widgets::color_picker;
+use egui::style::Margin;
+use egui::{Align2, Color32, Pos2, Rect, Rgba, Rounding, Stroke, Ui, Vec2};
+
+/// Width of an integer type, so that signed values are written in two's complement in other bases than decimal
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct IntegerWidth {
+    bits: u32,
+    signed: bool,
+}
+
+impl IntegerWidth {
+    /// Width of the integer type with the given bounds
+    fn new(min: f64, max: f64) -> Self {
+        let signed = min < 0.0;
+        Self {
+            bits: (max + 1.0).log2().ceil() as u32 + signed as u32,
+            signed,
+        }
+    }
+
+    /// Largest value of the unsigned type of the same width
+    fn mask(self) -> u128 {
+        u128::MAX >> (128 - self.bits)
+    }
+
+    /// Bits of `value`, as written in two's complement
+    fn bits_of(self, value: i128) -> u128 {
+        value as u128 & self.mask()
+    }
+
+    /// Value of the type with the given bits, bits out of the width being left untouched
+    fn value_of(self, bits: u128) -> i128 {
+        let sign_bit = 1 << (self.bits - 1);
+        if self.signed && bits & sign_bit != 0 && bits <= self.mask() {
+            (bits | !self.mask()) as i128
+        } else {
+            bits as i128
+        }
+    }
+}
+
+fn integer_width<T: Numeric>() -> IntegerWidth {
+    IntegerWidth::new(T::MIN.to_f64(), T::MAX.to_f64())
+}
+
+/// Number of digits needed to write any value of the given width in the given base
+fn integer_digits(width: IntegerWidth, format: IntegerFormat) -> usize {
+    let bits = width.bits as usize;
+    match format {
+        IntegerFormat::Decimal => 1,
+        IntegerFormat::Hexadecimal => bits.div_ceil(4),
+        IntegerFormat::Binary => bits,
+        IntegerFormat::Octal => bits.div_ceil(3),
+    }
+}
+
+fn format_integer(value: i128, format: IntegerFormat, width: IntegerWidth) -> String {
+    let digits = integer_digits(width, format);
+    let bits = width.bits_of(value);
+    match format {
+        IntegerFormat::Decimal => format!("{value}"),
+        IntegerFormat::Hexadecimal => format!("0x{bits:0>digits$x}"),
+        IntegerFormat::Binary => format!("0b{bits:0>digits$b}"),
+        IntegerFormat::Octal => format!("0o{bits:0>digits$o}"),
+    }
+}
+
+/// Parse an integer written by [format_integer], or with an explicit sign
+fn parse_integer(text: &str, format: IntegerFormat, width: IntegerWidth) -> Option<i128> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let (radix, prefix) = match format {
+        IntegerFormat::Decimal => (10, ""),
+        IntegerFormat::Hexadecimal => (16, "0x"),
+        IntegerFormat::Binary => (2, "0b"),
+        IntegerFormat::Octal => (8, "0o"),
+    };
+    let text = text.strip_prefix(prefix).unwrap_or(text);
+    let value = u128::from_str_radix(text, radix).ok()?;
+    if negative {
+        0i128.checked_sub_unsigned(value)
+    } else if format == IntegerFormat::Decimal {
+        i128::try_from(value).ok()
+    } else {
+        Some(width.value_of(value))
+    }
+}
+
+/// Text box evaluating math expressions when it loses focus.
+/// The typed text is kept while it is edited or invalid, with the error displayed on hover.
+/// The value is left untouched if the text was not edited, so focusing the text box
+/// doesn't round it to the displayed decimals or through `f64`.
+fn number_expression<T: Numeric>(
+    value: &mut T,
+    label: &str,
+    ui: &mut Ui,
+    range: Option<RangeInclusive<T>>,
+    options: &NumberOptions,
+) {
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+
+        let id = ui.make_persistent_id(label);
+        let error_id = id.with("error");
+        let edited_id = id.with("edited");
+        let mut text = ui.data().get_temp::<String>(id).unwrap_or_else(|| {
+            if T::INTEGRAL {
+                format_integer(value.to_f64() as i128, options.format, integer_width::<T>())
+            } else if let Some(decimals) = options.decimals {
+                format!("{:.*}", decimals, value.to_f64())
+            } else {
+                value.to_f64().to_string()
+            }
+        });
+        let error = ui.data().get_temp::<String>(error_id);
+        let mut edited = ui.data().get_temp::<bool>(edited_id).unwrap_or(false);
+        let text_color = error.as_ref().map(|_| ui.visuals().error_fg_color);
+
+        if !options.prefix.is_empty() {
+            ui.label(&options.prefix);
+        }
+        let mut response = ui.add(egui::TextEdit::singleline(&mut text).id(id).text_color_opt(text_color));
+        if !options.suffix.is_empty() {
+            ui.label(&options.suffix);
+        }
+
+        edited |= response.changed();
+
+        if response.lost_focus() && !edited {
+            ui.data().remove::<String>(id);
+        } else if response.lost_focus() {
+            match crate::expression::evaluate(&text, value.to_f64()) {
+                Ok(mut result) => {
+                    if let Some(step) = options.step.filter(|step| *step > 0.0) {
+                        result = (result / step).round() * step;
+                    }
+                    if T::INTEGRAL {
+                        result = result.round();
+                        // Read back in two's complement, like the value is displayed in other bases than decimal
+                        if options.format != IntegerFormat::Decimal && result > T::MAX.to_f64() {
+                            result = integer_width::<T>().value_of(result as u128) as f64;
+                        }
+                    }
+                    result = result.clamp(T::MIN.to_f64(), T::MAX.to_f64());
+                    if let Some(range) = range.filter(|_| options.clamp != SliderClamping::Never) {
+                        let (start, end) = (range.start().to_f64(), range.end().to_f64());
+                        result = result.clamp(start.min(end), start.max(end));
+                    }
+                    *value = T::from_f64(result);
+                    ui.data().remove::<String>(id);
+                    ui.data().remove::<String>(error_id);
+                    ui.data().remove::<bool>(edited_id);
+                }
+                Err(error) => {
+                    ui.data().insert_temp(id, text);
+                    ui.data().insert_temp(error_id, error);
+                    ui.data().insert_temp(edited_id, true);
+                }
+            }
+        } else if response.has_focus() {
+            if response.changed() {
+                ui.data().remove::<String>(error_id);
+            }
+            ui.data().insert_temp(id, text);
+            ui.data().insert_temp(edited_id, edited);
+        }
+
+        if let Some(error) = error {
+            response = response.on_hover_text(error);
+        }
+        response.on_hover_text("Type a value or an expression, like `1/60`, `2*pi` or `+=0.5`");
+    });
+}
+
+fn use_expression(ui: &Ui, options: &NumberOptions) -> bool {
+    options.expression || crate::expression_input(ui.ctx())
+}
+
+fn number_slider<T: Numeric>(value: &mut T, label: &str, ui: &mut Ui, range: RangeInclusive<T>, options: &NumberOptions) {
+    if use_expression(ui, options) {
+        return number_expression(value, label, ui, Some(range), options);
+    }
+
+    if options.clamp == SliderClamping::Always {
+        let (start, end) = (*range.start(), *range.end());
+        let (min, max) = if start <= end { (start, end) } else { (end, start) };
+        if *value < min {
+            *value = min;
+        } else if *value > max {
+            *value = max;
+        }
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        let mut slider = egui::Slider::new(value, range)
+            .prefix(&options.prefix)
+            .suffix(&options.suffix)
+            .logarithmic(options.logarithmic)
+            .clamp_to_range(options.clamp != SliderClamping::Never)
+            .smart_aim(options.smart_aim);
+        if let Some(step) = options.step {
+            slider = slider.step_by(step);
+        }
+        if let Some(decimals) = options.decimals {
+            slider = slider.fixed_decimals(decimals);
+        }
+        if T::INTEGRAL && options.format != IntegerFormat::Decimal {
+            let (format, width) = (options.format, integer_width::<T>());
+            slider = slider
+                .custom_formatter(move |n, _| format_integer(n as i128, format, width))
+                .custom_parser(move |text| parse_integer(text, format, width).map(|n| n as f64));
+        }
+        ui.add(slider);
+    });
+}
+
+fn number_drag_value<T: Numeric>(value: &mut T, label: &str, ui: &mut Ui, options: &NumberOptions) {
+    if use_expression(ui, options) {
+        return number_expression(value, label, ui, None, options);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        let mut drag_value = egui::DragValue::new(value)
+            .prefix(&options.prefix)
+            .suffix(&options.suffix);
+        if let Some(speed) = options.speed {
+            drag_value = drag_value.speed(speed);
+        }
+        if let Some(decimals) = options.decimals {
+            drag_value = drag_value.fixed_decimals(decimals);
+        }
+        if T::INTEGRAL && options.format != IntegerFormat::Decimal {
+            let (format, width) = (options.format, integer_width::<T>());
+            drag_value = drag_value
+                .custom_formatter(move |n, _| format_integer(n as i128, format, width))
+                .custom_parser(move |text| parse_integer(text, format, width).map(|n| n as f64));
+        }
+        let response = ui.add(drag_value);
+
+        // DragValue has no step of its own, so round the value after edition
+        if let Some(step) = options.step {
+            if response.changed() && step > 0.0 {
+                *value = T::from_f64((value.to_f64() / step).round() * step);
+            }
+        }
+    });
+}
 
 macro_rules! impl_inspect_float {
     ($($t:ty),+) => {
         $(
             impl crate::InspectNumber for $t {
                 fn inspect_with_slider(&mut self, label: &str, ui: &mut egui::Ui, min: f32, max: f32) {
-                    ui.horizontal(|ui| {
-                        ui.label(label.to_owned() + ":");
-                        ui.add(egui::Slider::new(self, (min as $t)..=(max as $t)));
-                    });
+                    self.inspect_with_range(label, ui, (min as $t)..=(max as $t), &NumberOptions::default());
+                }
+                fn inspect_with_range(&mut self, label: &str, ui: &mut egui::Ui, range: RangeInclusive<$t>, options: &NumberOptions) {
+                    number_slider(self, label, ui, range, options);
                 }
                 fn inspect_with_drag_value(&mut self, label: &str, ui: &mut egui::Ui) {
-                    ui.horizontal(|ui| {
-                        ui.label(label.to_owned() + ":");
-                        ui.add(egui::DragValue::new(self));
-                    });
+                    self.inspect_with_drag_value_options(label, ui, &NumberOptions::default());
+                }
+                fn inspect_with_drag_value_options(&mut self, label: &str, ui: &mut egui::Ui, options: &NumberOptions) {
+                    number_drag_value(self, label, ui, options);
                 }
             }
 
@@ -29,7 +296,16 @@ macro_rules! impl_inspect_float {
                     });
                 }
                 fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-                    self.inspect_with_slider(label, ui, 0.0f32, 100.0f32);
+                    self.inspect_with_drag_value(label, ui);
+                }
+                fn is_scalar() -> bool {
+                    true
+                }
+                fn duplicate(&self) -> Option<Self> {
+                    Some(*self)
+                }
+                fn default_factory() -> Option<fn() -> Self> {
+                    Some(Self::default)
                 }
             }
         )*
@@ -37,19 +313,47 @@ macro_rules! impl_inspect_float {
 }
 
 macro_rules! impl_inspect_int {
-    ($($t:ty),+) => {
-        $(
+    ($t:ty => $default_inspect_mut:expr) => {
         impl crate::InspectNumber for $t {
             fn inspect_with_slider(&mut self, label: &str, ui: &mut egui::Ui, min: f32, max: f32) {
+                self.inspect_with_range(label, ui, (min as $t)..=(max as $t), &NumberOptions::default());
+            }
+            fn inspect_with_range(&mut self, label: &str, ui: &mut egui::Ui, range: RangeInclusive<$t>, options: &NumberOptions) {
+                number_slider(self, label, ui, range, options);
+            }
+            fn inspect_with_drag_value(&mut self, label: &str, ui: &mut egui::Ui) {
+                self.inspect_with_drag_value_options(label, ui, &NumberOptions::default());
+            }
+            fn inspect_with_drag_value_options(&mut self, label: &str, ui: &mut egui::Ui, options: &NumberOptions) {
+                number_drag_value(self, label, ui, options);
+            }
+        }
+
+        impl crate::InspectInteger for $t {
+            fn inspect_with_format(&self, label: &str, ui: &mut egui::Ui, format: IntegerFormat) {
                 ui.horizontal(|ui| {
                     ui.label(label.to_owned() + ":");
-                    ui.add(egui::Slider::new(self, (min as $t)..=(max as $t)));
+                    ui.label(format_integer(*self as i128, format, IntegerWidth::new(<$t>::MIN as f64, <$t>::MAX as f64)));
                 });
             }
-            fn inspect_with_drag_value(&mut self, label: &str, ui: &mut egui::Ui) {
-                ui.horizontal(|ui| {
+            fn inspect_bits(&self, label: &str, ui: &mut egui::Ui, names: &[&str]) {
+                let mut value = *self;
+                ui.add_enabled_ui(false, |ui| value.inspect_mut_bits(label, ui, names));
+            }
+            fn inspect_mut_bits(&mut self, label: &str, ui: &mut egui::Ui, names: &[&str]) {
+                ui.horizontal_wrapped(|ui| {
                     ui.label(label.to_owned() + ":");
-                    ui.add(egui::DragValue::new(self));
+                    let bits: Vec<(u32, String)> = if names.is_empty() {
+                        (0..<$t>::BITS).rev().map(|bit| (bit, bit.to_string())).collect()
+                    } else {
+                        (0..<$t>::BITS).zip(names.iter().map(|name| name.to_string())).collect()
+                    };
+                    for (bit, name) in bits {
+                        let set = (*self >> bit) & 1 == 1;
+                        if ui.selectable_label(set, name).clicked() {
+                            *self ^= 1 << bit;
+                        }
+                    }
                 });
             }
         }
@@ -62,21 +366,201 @@ macro_rules! impl_inspect_int {
                 });
             }
             fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-                self.inspect_with_slider(label, ui, 0.0, 100.0);
+                ($default_inspect_mut)(self, label, ui);
+            }
+            fn is_scalar() -> bool {
+                true
+            }
+            fn duplicate(&self) -> Option<Self> {
+                Some(*self)
+            }
+            fn default_factory() -> Option<fn() -> Self> {
+                Some(Self::default)
             }
         }
+    };
+    ($($t:ty),+) => {
+        $(
+        impl_inspect_int!($t => |value: &mut $t, label: &str, ui: &mut egui::Ui| value.inspect_with_drag_value(label, ui));
         )*
-    }
+    };
 }
 
 impl_inspect_float!(f32, f64);
 
-impl_inspect_int!(i8, u8);
+impl_inspect_int!(i8);
+impl_inspect_int!(u8 => |value: &mut u8, label: &str, ui: &mut egui::Ui| value.inspect_with_range(label, ui, u8::MIN..=u8::MAX, &NumberOptions::default()));
 impl_inspect_int!(i16, u16);
 impl_inspect_int!(i32, u32);
 impl_inspect_int!(i64, u64);
 impl_inspect_int!(isize, usize);
 
+/// Edit a value through its textual representation.
+/// The text being typed is kept in egui memory, and only applied once it parses.
+fn inspect_mut_parsed<T: FromStr + ToString>(value: &mut T, label: &str, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+
+        let id = ui.make_persistent_id(label);
+        let mut text = ui.data().get_temp::<String>(id).unwrap_or_else(|| value.to_string());
+        let text_color = match text.parse::<T>() {
+            Ok(_) => None,
+            Err(_) => Some(ui.visuals().error_fg_color),
+        };
+
+        let response = ui.add(egui::TextEdit::singleline(&mut text).id(id).text_color_opt(text_color));
+        if response.changed() {
+            if let Ok(parsed) = text.parse() {
+                *value = parsed;
+            }
+        }
+
+        if response.has_focus() {
+            ui.data().insert_temp(id, text);
+        } else {
+            ui.data().remove::<String>(id);
+        }
+    });
+}
+
+macro_rules! impl_inspect_parsed {
+    ($($t:ty => $default_factory:expr),+) => {
+        $(
+        impl crate::EguiInspect for $t {
+            fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+                ui.horizontal(|ui| {
+                    ui.label(label.to_owned() + ":");
+                    ui.label(self.to_string());
+                });
+            }
+            fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+                inspect_mut_parsed(self, label, ui);
+            }
+            fn is_scalar() -> bool {
+                true
+            }
+            fn duplicate(&self) -> Option<Self> {
+                Some(*self)
+            }
+            fn default_factory() -> Option<fn() -> Self> {
+                $default_factory
+            }
+        }
+        )*
+    }
+}
+
+// egui widgets only handle numbers fitting in a f64, so 128 bits integers are edited as text
+impl_inspect_parsed!(
+    i128 => Some(i128::default),
+    u128 => Some(u128::default),
+    NonZeroI128 => None,
+    NonZeroU128 => None
+);
+
+macro_rules! impl_inspect_non_zero {
+    ($($nz:ty => $t:ty),+) => {
+        $(
+        impl crate::EguiInspect for $nz {
+            fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+                self.get().inspect(label, ui);
+            }
+            fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+                let mut value: $t = self.get();
+                value.inspect_with_drag_value(label, ui);
+                // Zero is refused, the previous value is kept instead
+                if let Some(value) = <$nz>::new(value) {
+                    *self = value;
+                }
+            }
+            fn is_scalar() -> bool {
+                true
+            }
+            fn duplicate(&self) -> Option<Self> {
+                Some(*self)
+            }
+        }
+        )*
+    }
+}
+
+impl_inspect_non_zero!(NonZeroI8 => i8, NonZeroU8 => u8);
+impl_inspect_non_zero!(NonZeroI16 => i16, NonZeroU16 => u16);
+impl_inspect_non_zero!(NonZeroI32 => i32, NonZeroU32 => u32);
+impl_inspect_non_zero!(NonZeroI64 => i64, NonZeroU64 => u64);
+impl_inspect_non_zero!(NonZeroIsize => isize, NonZeroUsize => usize);
+
+/// Value built by the default factory of `T`, which must have been checked to exist
+fn new_default<T: crate::EguiInspect>() -> T {
+    T::default_factory().expect("the default factory should have been checked")()
+}
+
+/// Default factory building a value from those of the given types, if they all have one.
+/// Their values are built in `$build` with [new_default].
+macro_rules! default_factory_from {
+    ($($t:ident),+ => $build:expr) => {{
+        $($t::default_factory()?;)+
+        Some(|| $build)
+    }};
+}
+
+macro_rules! impl_inspect_number_wrapper {
+    ($($wrapper:ident),+) => {
+        $(
+        impl<T: crate::EguiInspect> crate::EguiInspect for $wrapper<T> {
+            fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+                self.0.inspect(label, ui);
+            }
+            fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+                self.0.inspect_mut(label, ui);
+            }
+            fn is_scalar() -> bool {
+                T::is_scalar()
+            }
+            fn duplicate(&self) -> Option<Self> {
+                self.0.duplicate().map($wrapper)
+            }
+            fn default_factory() -> Option<fn() -> Self> {
+                default_factory_from!(T => $wrapper(new_default::<T>()))
+            }
+        }
+        )*
+    }
+}
+
+impl_inspect_number_wrapper!(Wrapping, Saturating);
+
+impl crate::EguiInspect for char {
+    fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            ui.label(self.to_string());
+        });
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            let mut text = self.to_string();
+            let response = ui.add(egui::TextEdit::singleline(&mut text).desired_width(ui.spacing().interact_size.x));
+            if response.changed() {
+                // Keep the newly typed character, or the previous one if it has been erased
+                if let Some(c) = text.chars().find(|c| c != self) {
+                    *self = c;
+                }
+            }
+        });
+    }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
 impl crate::EguiInspect for &'static str {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
@@ -91,6 +575,12 @@ impl crate::EguiInspect for &'static str {
                 .on_hover_text("inspect_mut is not implemented for &'static str");
         });
     }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
 }
 
 impl crate::EguiInspect for String {
@@ -103,6 +593,12 @@ impl crate::EguiInspect for String {
     fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
         self.inspect_mut_singleline(label, ui);
     }
+    fn duplicate(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
 }
 
 impl crate::InspectString for String {
@@ -128,53 +624,1749 @@ impl crate::EguiInspect for bool {
     fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
         ui.checkbox(self, label);
     }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+impl crate::EguiInspect for () {
+    fn inspect(&self, _label: &str, _ui: &mut Ui) {}
+    fn inspect_mut(&mut self, _label: &str, _ui: &mut Ui) {}
+    fn duplicate(&self) -> Option<Self> {
+        Some(())
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+impl<T: ?Sized> crate::EguiInspect for PhantomData<T> {
+    fn inspect(&self, _label: &str, _ui: &mut Ui) {}
+    fn inspect_mut(&mut self, _label: &str, _ui: &mut Ui) {}
+    fn duplicate(&self) -> Option<Self> {
+        Some(PhantomData)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
 }
 
 impl<T: crate::EguiInspect, const N: usize> crate::EguiInspect for [T; N] {
     fn inspect(&self, label: &str, ui: &mut Ui) {
-        egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", N).as_str())).show(ui, |ui| {
-            for item in self.iter() {
-                item.inspect("item", ui);
-            }
-        });
+        if T::is_scalar() && N <= 4 {
+            return self.inspect_inline(label, ui);
+        }
+        self.inspect_with_options(label, ui, &CollectionOptions::default());
     }
 
     fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
-        egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", N).as_str())).show(ui, |ui| {
-            for item in self.iter_mut() {
-                item.inspect_mut("item", ui);
-            }
+        if T::is_scalar() && N <= 4 {
+            return self.inspect_mut_inline(label, ui);
+        }
+        self.inspect_mut_with_options(label, ui, &CollectionOptions::default());
+    }
+
+    fn duplicate(&self) -> Option<Self> {
+        let items: Vec<T> = self.iter().map(T::duplicate).collect::<Option<_>>()?;
+        items.try_into().ok()
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        default_factory_from!(T => std::array::from_fn(|_| new_default::<T>()))
+    }
+}
+
+impl<T: crate::EguiInspect, const N: usize> crate::InspectCollection<T> for [T; N] {
+    fn inspect_with_options(&self, label: &str, ui: &mut Ui, options: &CollectionOptions<T>) {
+        inspect_items(self, label, ui, options);
+    }
+
+    /// Items can't be added to an array, so `new_item` is ignored
+    fn inspect_mut_with_options(&mut self, label: &str, ui: &mut Ui, options: &CollectionOptions<T>) {
+        let id = ui.make_persistent_id(label);
+        egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", N).as_str()))
+            .id_source(label).show(ui, |ui| {
+            show_items(ui, id, N, T::is_scalar(), |ui, index| {
+                let item_label = item_label(&self[index], index, options);
+                ui.push_id(item_id_source(&item_label, index, options), |ui| {
+                    self[index].inspect_mut(&item_label, ui);
+                }).response
+            });
         });
     }
 }
 
-impl<T: crate::EguiInspect + Default> crate::EguiInspect for Vec<T> {
-    fn inspect(&self, label: &str, ui: &mut Ui) {
-        egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str())).show(ui, |ui| {
-            for item in self.iter() {
-                item.inspect("item", ui);
-            }
+/// Label of the item at `index` of a collection: its index like `[0]`, unless customized by `options`
+fn item_label<T>(item: &T, index: usize, options: &CollectionOptions<T>) -> String {
+    match options.item_label {
+        Some(item_label) => item_label(item),
+        None => format!("[{}]", index),
+    }
+}
+
+/// Scope of the item at `index` of a collection: its custom label if any, so that its state follows it
+/// when the collection is reordered, or its index otherwise
+fn item_id_source<T>(item_label: &str, index: usize, options: &CollectionOptions<T>) -> egui::Id {
+    match options.item_label {
+        Some(_) => egui::Id::new(item_label),
+        None => egui::Id::new(index),
+    }
+}
+
+/// Read-only display of the items of a `Vec` or an array
+fn inspect_items<T: crate::EguiInspect>(items: &[T], label: &str, ui: &mut Ui, options: &CollectionOptions<T>) {
+    let id = ui.make_persistent_id(label);
+    egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", items.len()).as_str()))
+        .id_source(label).show(ui, |ui| {
+        show_items(ui, id, items.len(), T::is_scalar(), |ui, index| {
+            let item_label = item_label(&items[index], index, options);
+            ui.push_id(item_id_source(&item_label, index, options), |ui| {
+                items[index].inspect(&item_label, ui);
+            }).response
         });
+    });
+}
+
+/// Label of the element at `index` of an inline collection of `len` elements
+fn inline_item_label(index: usize, len: usize) -> String {
+    match (len, index) {
+        (..=4, 0) => String::from("x"),
+        (..=4, 1) => String::from("y"),
+        (..=4, 2) => String::from("z"),
+        (..=4, 3) => String::from("w"),
+        _ => format!("[{}]", index),
     }
+}
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
-        ui.horizontal_top(|ui| {
-            egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
-                .id_source(label).show(ui, |ui| {
-                for item in self.iter_mut() {
-                    item.inspect_mut("item", ui);
+fn inspect_inline<T: crate::EguiInspect>(items: &[T], label: &str, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        for (index, item) in items.iter().enumerate() {
+            ui.push_id(index, |ui| item.inspect(&inline_item_label(index, items.len()), ui));
+        }
+    });
+}
+
+fn inspect_mut_inline<T: crate::EguiInspect>(items: &mut [T], label: &str, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        let len = items.len();
+        for (index, item) in items.iter_mut().enumerate() {
+            ui.push_id(index, |ui| item.inspect_mut(&inline_item_label(index, len), ui));
+        }
+    });
+}
+
+impl<T: crate::EguiInspect, const N: usize> crate::InspectInline for [T; N] {
+    fn inspect_inline(&self, label: &str, ui: &mut Ui) {
+        inspect_inline(self, label, ui);
+    }
+
+    fn inspect_mut_inline(&mut self, label: &str, ui: &mut Ui) {
+        inspect_mut_inline(self, label, ui);
+    }
+}
+
+impl<T: crate::EguiInspect> crate::InspectInline for Vec<T> {
+    fn inspect_inline(&self, label: &str, ui: &mut Ui) {
+        inspect_inline(self, label, ui);
+    }
+
+    fn inspect_mut_inline(&mut self, label: &str, ui: &mut Ui) {
+        inspect_mut_inline(self, label, ui);
+    }
+}
+
+/// Pagination of a large collection, kept in egui memory between frames
+#[derive(Clone)]
+struct PageState {
+    page: usize,
+    page_size: usize,
+    jump: String,
+}
+
+/// Number of scalar items displayed at once, scrolling through the others
+const VISIBLE_ROWS: usize = 20;
+
+/// Display the items of a collection of `len` elements with `add_item`, one page at a time.
+/// Large pages are also virtualized, so only the visible items are displayed.
+/// `add_item` scopes each item with `ui.push_id`, so items with the same label don't share their state.
+fn show_items(
+    ui: &mut Ui,
+    id: egui::Id,
+    len: usize,
+    scalar: bool,
+    mut add_item: impl FnMut(&mut Ui, usize) -> egui::Response,
+) {
+    let state_id = id.with("page");
+    let state = ui.data().get_temp::<PageState>(state_id);
+    let mut state = state.unwrap_or_else(|| PageState {
+        page: 0,
+        page_size: crate::page_size(ui.ctx()),
+        jump: String::new(),
+    });
+    let mut jumped = None;
+
+    // Also shown when everything fits in one page, so that the page size can be lowered again
+    if len > state.page_size || len > VISIBLE_ROWS {
+        ui.horizontal(|ui| {
+            if len > state.page_size {
+                let pages = len.div_ceil(state.page_size);
+                if ui.add_enabled(state.page > 0, egui::Button::new("◀")).clicked() {
+                    state.page -= 1;
                 }
-            });
+                ui.label(format!("Page {} / {}", state.page + 1, pages));
+                if ui.add_enabled(state.page + 1 < pages, egui::Button::new("▶")).clicked() {
+                    state.page += 1;
+                }
+            }
+            ui.add(egui::DragValue::new(&mut state.page_size).clamp_range(1..=10_000).suffix(" per page"));
 
-            let response = ui.button("Add");
-            if response.clicked() {
-                self.push(T::default());
+            let response = ui.add(egui::TextEdit::singleline(&mut state.jump).hint_text("Go to index").desired_width(80.0));
+            if response.lost_focus() && ui.input().key_pressed(egui::Key::Enter) {
+                if let Some(index) = state.jump.trim().parse::<usize>().ok().filter(|index| *index < len) {
+                    state.page = index / state.page_size;
+                    state.jump.clear();
+                    jumped = Some(index);
+                }
+            }
+        });
+    }
+
+    // The collection may have shrunk since the page was selected
+    state.page = state.page.min(len.saturating_sub(1) / state.page_size);
+    let start = state.page * state.page_size;
+    let end = len.min(start + state.page_size);
+    ui.data().insert_temp(state_id, state);
+
+    let row_height = ui.spacing().interact_size.y;
+    let row_spacing = row_height + ui.spacing().item_spacing.y;
+    let mut scroll_area = egui::ScrollArea::vertical()
+        .id_source(id.with("rows"))
+        .max_height(row_spacing * VISIBLE_ROWS as f32);
+
+    if end - start <= VISIBLE_ROWS {
+        for index in start..end {
+            let response = add_item(ui, index);
+            if jumped == Some(index) {
+                ui.scroll_to_rect(response.rect, Some(egui::Align::Center));
+            }
+        }
+    } else if scalar {
+        if let Some(index) = jumped {
+            scroll_area = scroll_area.vertical_scroll_offset((index - start) as f32 * row_spacing);
+        }
+        scroll_area.show_rows(ui, row_height, end - start, |ui, rows| {
+            for index in rows.map(|row| start + row) {
+                add_item(ui, index);
             }
+        });
+    } else {
+        // Composite items have different heights, measured whenever they are displayed
+        let heights_id = id.with(("heights", start));
+        let mut heights = ui.data().get_temp::<Vec<f32>>(heights_id).unwrap_or_default();
+        heights.resize(end - start, row_spacing);
+        if let Some(index) = jumped {
+            scroll_area = scroll_area.vertical_scroll_offset(heights[..index - start].iter().sum());
+        }
+        scroll_area.show_viewport(ui, |ui, viewport| {
+            let mut row = 0;
+            let mut top = 0.0;
+            while row < heights.len() && top + heights[row] < viewport.min.y {
+                top += heights[row];
+                row += 1;
+            }
+            ui.add_space(top);
 
-            let response = ui.button("Pop");
-            if response.clicked() {
-                self.pop();
+            while row < heights.len() && top < viewport.max.y {
+                let index = start + row;
+                let response = add_item(ui, index);
+                heights[row] = response.rect.height() + ui.spacing().item_spacing.y;
+                top += heights[row];
+                row += 1;
             }
+            ui.add_space(heights[row..].iter().sum());
         });
+        ui.data().insert_temp(heights_id, heights);
+    }
+}
+
+/// Edition of a `Vec` requested from the controls of an item, applied once all items are drawn
+enum VecEdit {
+    InsertBefore(usize),
+    InsertAfter(usize),
+    Duplicate(usize),
+    Remove(usize),
+    Move { from: usize, to: usize },
+}
+
+/// Edit the items of a `Vec` in place: each item gets a drag handle to reorder it,
+/// and a menu to insert, duplicate or remove items.
+fn inspect_mut_vec_items<T: crate::EguiInspect>(
+    items: &mut Vec<T>,
+    id: egui::Id,
+    ui: &mut Ui,
+    new_item: Option<fn() -> T>,
+    options: &CollectionOptions<T>,
+) {
+    let dragged_id = id.with("dragged");
+    let dragged = ui.data().get_temp::<usize>(dragged_id);
+    let mut edit = None;
+    // Index and rect of every displayed item, to find where a dragged item is dropped
+    let mut rows = Vec::new();
+
+    show_items(ui, id, items.len(), T::is_scalar(), |ui, index| {
+        let item = &mut items[index];
+        let item_label = item_label(item, index, options);
+        let row = ui.push_id(item_id_source(&item_label, index, options), |ui| {
+            ui.horizontal_top(|ui| {
+                let handle = ui
+                    .add(egui::Label::new("☰").sense(egui::Sense::drag()))
+                    .on_hover_cursor(egui::CursorIcon::Grab)
+                    .on_hover_text("Drag to reorder");
+                if handle.drag_started() {
+                    ui.data().insert_temp(dragged_id, index);
+                }
+
+                ui.menu_button("⏷", |ui| {
+                    if new_item.is_some() && ui.button("Insert before").clicked() {
+                        edit = Some(VecEdit::InsertBefore(index));
+                        ui.close_menu();
+                    }
+                    if new_item.is_some() && ui.button("Insert after").clicked() {
+                        edit = Some(VecEdit::InsertAfter(index));
+                        ui.close_menu();
+                    }
+                    // Only offered when the item can actually be duplicated
+                    if item.duplicate().is_some() && ui.button("Duplicate").clicked() {
+                        edit = Some(VecEdit::Duplicate(index));
+                        ui.close_menu();
+                    }
+                    if ui.button("Remove").clicked() {
+                        edit = Some(VecEdit::Remove(index));
+                        ui.close_menu();
+                    }
+                });
+
+                item.inspect_mut(&item_label, ui);
+            });
+        }).response;
+        rows.push((index, row.rect));
+        row
+    });
+
+    // While an item is dragged, show where it would be dropped among the displayed items
+    if let (Some(from), Some(&(last_index, last_row))) = (dragged, rows.last()) {
+        let target = ui.input().pointer.interact_pos().map(|pointer| {
+            match rows.iter().find(|(_, row)| pointer.y < row.center().y) {
+                Some(&(index, row)) => (index, row.top()),
+                None => (last_index + 1, last_row.bottom()),
+            }
+        });
+        if let Some((_, y)) = target {
+            let stroke = ui.visuals().selection.stroke;
+            ui.painter().hline(ui.min_rect().x_range(), y, stroke);
+            ui.output().cursor_icon = egui::CursorIcon::Grabbing;
+        }
+        if ui.input().pointer.any_released() {
+            ui.data().remove::<usize>(dragged_id);
+            if let Some((to, _)) = target {
+                edit = Some(VecEdit::Move { from, to });
+            }
+        }
+    } else if dragged.is_some() {
+        ui.data().remove::<usize>(dragged_id);
+    }
+
+    match (edit, new_item) {
+        (Some(VecEdit::InsertBefore(index)), Some(new_item)) => items.insert(index, new_item()),
+        (Some(VecEdit::InsertAfter(index)), Some(new_item)) => items.insert(index + 1, new_item()),
+        (Some(VecEdit::Duplicate(index)), _) => {
+            if let Some(item) = items[index].duplicate() {
+                items.insert(index + 1, item);
+            }
+        }
+        (Some(VecEdit::Remove(index)), _) => {
+            items.remove(index);
+        }
+        // `to` is an insertion point among the items before the move
+        (Some(VecEdit::Move { from, to }), _) if from < items.len() && to != from && to != from + 1 => {
+            let item = items.remove(from);
+            items.insert(if to > from { to - 1 } else { to }, item);
+        }
+        _ => {}
+    }
+}
+
+/// Button removing every item of a collection, asking for confirmation first
+fn clear_button(ui: &mut Ui, id: egui::Id, len: usize) -> bool {
+    let confirm_id = id.with("confirm_clear");
+    if !ui.data().get_temp::<bool>(confirm_id).unwrap_or(false) {
+        if ui.add_enabled(len > 0, egui::Button::new("Clear")).clicked() {
+            ui.data().insert_temp(confirm_id, true);
+        }
+        return false;
+    }
+
+    ui.label(format!("Remove {} items?", len));
+    let confirmed = ui.button("Yes").clicked();
+    if confirmed || ui.button("No").clicked() {
+        ui.data().remove::<bool>(confirm_id);
+    }
+    confirmed
+}
+
+/// Items are added with the `new_item` of `options`, or the default factory of the items, if any
+fn inspect_mut_vec<T: crate::EguiInspect>(items: &mut Vec<T>, label: &str, ui: &mut Ui, options: &CollectionOptions<T>) {
+    let id = ui.make_persistent_id(label);
+    let new_item = options.new_item.or_else(T::default_factory);
+    ui.horizontal_top(|ui| {
+        egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", items.len()).as_str()))
+            .id_source(label).show(ui, |ui| {
+            inspect_mut_vec_items(items, id, ui, new_item, options);
+        });
+
+        if let Some(new_item) = new_item {
+            let response = ui.button("Add");
+            if response.clicked() {
+                items.push(new_item());
+            }
+        }
+
+        let response = ui.button("Pop");
+        if response.clicked() {
+            items.pop();
+        }
+
+        if clear_button(ui, id, items.len()) {
+            items.clear();
+        }
+    });
+}
+
+impl<T: crate::EguiInspect> crate::EguiInspect for Vec<T> {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        self.inspect_with_options(label, ui, &CollectionOptions::default());
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        self.inspect_mut_with_options(label, ui, &CollectionOptions::default());
+    }
+
+    fn duplicate(&self) -> Option<Self> {
+        self.iter().map(T::duplicate).collect()
+    }
+
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Vec::new)
+    }
+}
+
+impl<T: crate::EguiInspect> crate::InspectCollection<T> for Vec<T> {
+    fn inspect_with_options(&self, label: &str, ui: &mut Ui, options: &CollectionOptions<T>) {
+        inspect_items(self, label, ui, options);
+    }
+
+    fn inspect_mut_with_options(&mut self, label: &str, ui: &mut Ui, options: &CollectionOptions<T>) {
+        inspect_mut_vec(self, label, ui, options);
+    }
+}
+
+macro_rules! impl_inspect_deque {
+    ($($deque:ident),+) => {
+        $(
+        impl<T: crate::EguiInspect> crate::EguiInspect for $deque<T> {
+            fn inspect(&self, label: &str, ui: &mut Ui) {
+                egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
+                    .id_source(label).show(ui, |ui| {
+                    for (index, item) in self.iter().enumerate() {
+                        ui.push_id(index, |ui| item.inspect(&format!("[{}]", index), ui));
+                    }
+                });
+            }
+
+            fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+                ui.horizontal_top(|ui| {
+                    egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
+                        .id_source(label).show(ui, |ui| {
+                        for (index, item) in self.iter_mut().enumerate() {
+                            ui.push_id(index, |ui| item.inspect_mut(&format!("[{}]", index), ui));
+                        }
+                    });
+
+                    if let Some(new_item) = T::default_factory() {
+                        if ui.button("Push front").clicked() {
+                            self.push_front(new_item());
+                        }
+                        if ui.button("Push back").clicked() {
+                            self.push_back(new_item());
+                        }
+                    }
+                    if ui.button("Pop front").clicked() {
+                        self.pop_front();
+                    }
+                    if ui.button("Pop back").clicked() {
+                        self.pop_back();
+                    }
+                });
+            }
+
+            fn duplicate(&self) -> Option<Self> {
+                self.iter().map(T::duplicate).collect()
+            }
+
+            fn default_factory() -> Option<fn() -> Self> {
+                Some($deque::new)
+            }
+        }
+        )*
+    };
+}
+
+impl_inspect_deque!(VecDeque, LinkedList);
+
+/// Pending edits of a set or a heap, kept in egui memory between frames.
+/// Their items can't be mutated in place, so they are edited through a draft confirmed with "Ok".
+struct DraftEditState<T> {
+    new_item: Option<T>,
+    /// The item being edited, and its draft
+    editing: Option<(T, T)>,
+    error: Option<String>,
+}
+
+impl<T> Default for DraftEditState<T> {
+    fn default() -> Self {
+        Self {
+            new_item: None,
+            editing: None,
+            error: None,
+        }
+    }
+}
+
+/// Edit states are shared, so that the items don't need to be `Clone` to be kept in egui memory
+fn load_edit_state<S: Default + Send + 'static>(ui: &Ui, id: egui::Id) -> Arc<Mutex<S>> {
+    ui.data().get_temp_mut_or_default::<Arc<Mutex<S>>>(id).clone()
+}
+
+/// Edition of a set or a heap requested from the row of an item
+enum DraftEdit {
+    Remove(usize),
+    Confirm,
+}
+
+/// Row of an item of a set or a heap, displayed read-only unless it is being edited through `state`
+fn draft_item_row<T: crate::EguiInspect + PartialEq>(
+    item: &T,
+    index: usize,
+    ui: &mut Ui,
+    state: &mut DraftEditState<T>,
+) -> Option<DraftEdit> {
+    let mut edit = None;
+    ui.horizontal_top(|ui| {
+        if ui.small_button("Remove").clicked() {
+            edit = Some(DraftEdit::Remove(index));
+        }
+        if ui.small_button("Edit").clicked() {
+            match item.duplicate().zip(item.duplicate()) {
+                Some(editing) => state.editing = Some(editing),
+                None => state.error = Some("This item can't be copied to be edited".to_owned()),
+            }
+        }
+        ui.vertical(|ui| item.inspect("item", ui));
+    });
+
+    let mut cancel = false;
+    if let Some((original, draft)) = &mut state.editing {
+        if original == item {
+            ui.horizontal_top(|ui| {
+                ui.vertical(|ui| draft.inspect_mut("Edited item", ui));
+                if ui.button("Ok").clicked() {
+                    edit = Some(DraftEdit::Confirm);
+                }
+                cancel = ui.button("Cancel").clicked();
+            });
+        }
+    }
+    if cancel {
+        state.editing = None;
+        state.error = None;
+    }
+    edit
+}
+
+/// Draft of a new item, if the items have a default factory. Returns whether "Add" was clicked.
+fn draft_new_item<T: crate::EguiInspect>(ui: &mut Ui, state: &mut DraftEditState<T>) -> bool {
+    let Some(factory) = T::default_factory() else {
+        return false;
+    };
+    ui.horizontal_top(|ui| {
+        ui.vertical(|ui| state.new_item.get_or_insert_with(factory).inspect_mut("New item", ui));
+        ui.button("Add").clicked()
+    })
+    .inner
+}
+
+macro_rules! impl_inspect_set {
+    ($set:ident, $($item_bound:tt)+) => {
+        impl<T> crate::EguiInspect for $set<T>
+        where
+            T: crate::EguiInspect + Send + 'static + $($item_bound)+,
+        {
+            fn inspect(&self, label: &str, ui: &mut Ui) {
+                egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
+                    .id_source(label).show(ui, |ui| {
+                    for (index, item) in self.iter().enumerate() {
+                        ui.push_id(index, |ui| item.inspect("item", ui));
+                    }
+                });
+            }
+
+            fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+                let id = ui.make_persistent_id(label);
+                let state = load_edit_state::<DraftEditState<T>>(ui, id);
+                let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+
+                let mut edit = None;
+                let mut add = false;
+                egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
+                    .id_source(id).show(ui, |ui| {
+                    for (index, item) in self.iter().enumerate() {
+                        if let Some(item_edit) = ui.push_id(index, |ui| draft_item_row(item, index, ui, &mut state)).inner {
+                            edit = Some(item_edit);
+                        }
+                    }
+
+                    add = draft_new_item(ui, &mut state);
+
+                    if let Some(error) = &state.error {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                });
+
+                match edit {
+                    Some(DraftEdit::Remove(index)) => {
+                        let mut position = 0;
+                        self.retain(|_| {
+                            position += 1;
+                            position - 1 != index
+                        });
+                        state.error = None;
+                    }
+                    Some(DraftEdit::Confirm) => {
+                        if let Some((original, edited)) = state.editing.take() {
+                            if original != edited && self.contains(&edited) {
+                                state.error = Some("This item is already in the set".to_owned());
+                                state.editing = Some((original, edited));
+                            } else {
+                                self.remove(&original);
+                                self.insert(edited);
+                                state.error = None;
+                            }
+                        }
+                    }
+                    None => {}
+                }
+
+                if add {
+                    if let Some(new_item) = state.new_item.take() {
+                        if self.contains(&new_item) {
+                            state.error = Some("This item is already in the set".to_owned());
+                            state.new_item = Some(new_item);
+                        } else {
+                            self.insert(new_item);
+                            state.error = None;
+                        }
+                    }
+                }
+            }
+
+            fn duplicate(&self) -> Option<Self> {
+                self.iter().map(T::duplicate).collect()
+            }
+
+            fn default_factory() -> Option<fn() -> Self> {
+                Some($set::new)
+            }
+        }
+    };
+}
+
+impl_inspect_set!(HashSet, Hash + Eq);
+impl_inspect_set!(BTreeSet, Ord);
+
+impl<T: crate::EguiInspect + Ord + Send + 'static> crate::EguiInspect for BinaryHeap<T> {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
+            .id_source(label).show(ui, |ui| {
+            for (index, item) in self.iter().enumerate() {
+                ui.push_id(index, |ui| item.inspect("item", ui));
+            }
+        });
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        let id = ui.make_persistent_id(label);
+        let state = load_edit_state::<DraftEditState<T>>(ui, id);
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let mut edit = None;
+        ui.horizontal_top(|ui| {
+            egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
+                .id_source(id).show(ui, |ui| {
+                for (index, item) in self.iter().enumerate() {
+                    if let Some(item_edit) = ui.push_id(index, |ui| draft_item_row(item, index, ui, &mut state)).inner {
+                        edit = Some(item_edit);
+                    }
+                }
+
+                if let Some(error) = &state.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+
+            if let Some(new_item) = T::default_factory() {
+                if ui.button("Push").clicked() {
+                    self.push(new_item());
+                }
+            }
+            if ui.button("Pop").clicked() {
+                self.pop();
+            }
+        });
+
+        // The heap is rebuilt from its items in their current order
+        match edit {
+            Some(DraftEdit::Remove(index)) => {
+                let mut items = std::mem::take(self).into_vec();
+                items.remove(index);
+                *self = BinaryHeap::from(items);
+                state.error = None;
+            }
+            Some(DraftEdit::Confirm) => {
+                if let Some((original, edited)) = state.editing.take() {
+                    let mut items = std::mem::take(self).into_vec();
+                    if let Some(item) = items.iter_mut().find(|item| **item == original) {
+                        *item = edited;
+                    }
+                    *self = BinaryHeap::from(items);
+                    state.error = None;
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn duplicate(&self) -> Option<Self> {
+        self.iter().map(T::duplicate).collect()
+    }
+
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(BinaryHeap::new)
+    }
+}
+
+macro_rules! impl_inspect_tuple {
+    ($(($($idx:tt $t:ident),+)),+) => {
+        $(
+        impl<$($t: crate::EguiInspect),+> crate::EguiInspect for ($($t,)+) {
+            fn inspect(&self, label: &str, ui: &mut Ui) {
+                if $(<$t as crate::EguiInspect>::is_scalar())&&+ {
+                    ui.horizontal(|ui| {
+                        ui.label(label.to_owned() + ":");
+                        $(ui.push_id($idx, |ui| self.$idx.inspect(stringify!($idx), ui));)+
+                    });
+                } else {
+                    egui::CollapsingHeader::new(label).show(ui, |ui| {
+                        $(ui.push_id($idx, |ui| self.$idx.inspect(stringify!($idx), ui));)+
+                    });
+                }
+            }
+
+            fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+                if $(<$t as crate::EguiInspect>::is_scalar())&&+ {
+                    ui.horizontal(|ui| {
+                        ui.label(label.to_owned() + ":");
+                        $(ui.push_id($idx, |ui| self.$idx.inspect_mut(stringify!($idx), ui));)+
+                    });
+                } else {
+                    egui::CollapsingHeader::new(label).show(ui, |ui| {
+                        $(ui.push_id($idx, |ui| self.$idx.inspect_mut(stringify!($idx), ui));)+
+                    });
+                }
+            }
+
+            fn duplicate(&self) -> Option<Self> {
+                Some(($(self.$idx.duplicate()?,)+))
+            }
+            fn default_factory() -> Option<fn() -> Self> {
+                default_factory_from!($($t),+ => ($(new_default::<$t>(),)+))
+            }
+        }
+        )+
+    };
+}
+
+impl_inspect_tuple!(
+    (0 A),
+    (0 A, 1 B),
+    (0 A, 1 B, 2 C),
+    (0 A, 1 B, 2 C, 3 D),
+    (0 A, 1 B, 2 C, 3 D, 4 E),
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F),
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G),
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H),
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I),
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J),
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K),
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K, 11 L)
+);
+
+/// Pending edits of a map, kept in egui memory between frames
+struct MapEditState<K> {
+    new_key: Option<K>,
+    /// The key being renamed, and its draft
+    renaming: Option<(K, K)>,
+    error: Option<String>,
+}
+
+impl<K> Default for MapEditState<K> {
+    fn default() -> Self {
+        Self {
+            new_key: None,
+            renaming: None,
+            error: None,
+        }
+    }
+}
+
+/// Edition of a map requested from the row of an entry
+enum MapEdit {
+    Remove(usize),
+    Rename,
+    CancelRename,
+}
+
+macro_rules! impl_inspect_map {
+    ($map:ident, $($key_bound:tt)+) => {
+        impl<K, V> crate::EguiInspect for $map<K, V>
+        where
+            K: crate::EguiInspect + ToString + Send + 'static + $($key_bound)+,
+            V: crate::EguiInspect,
+        {
+            fn inspect(&self, label: &str, ui: &mut Ui) {
+                egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
+                    .id_source(label).show(ui, |ui| {
+                    for (key, value) in self.iter() {
+                        let key = key.to_string();
+                        ui.push_id(&key, |ui| value.inspect(&key, ui));
+                    }
+                });
+            }
+
+            fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+                let id = ui.make_persistent_id(label);
+                let state = load_edit_state::<MapEditState<K>>(ui, id);
+                let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+
+                let mut edit = None;
+                let mut insert = false;
+                egui::CollapsingHeader::new(label.to_string().add(format!("[{}]", self.len()).as_str()))
+                    .id_source(id).show(ui, |ui| {
+                    for (index, (key, value)) in self.iter_mut().enumerate() {
+                        // Entries are scoped by key, so their state follows them when the map changes
+                        ui.push_id(key.to_string(), |ui| {
+                            ui.horizontal_top(|ui| {
+                                if ui.small_button("Remove").clicked() {
+                                    edit = Some(MapEdit::Remove(index));
+                                }
+                                if ui.small_button("Rename").clicked() {
+                                    match key.duplicate().zip(key.duplicate()) {
+                                        Some(renaming) => state.renaming = Some(renaming),
+                                        None => state.error = Some("This key can't be copied to be renamed".to_owned()),
+                                    }
+                                }
+                                ui.vertical(|ui| value.inspect_mut(&key.to_string(), ui));
+                            });
+
+                            if let Some((original, draft)) = &mut state.renaming {
+                                if original == key {
+                                    ui.horizontal(|ui| {
+                                        draft.inspect_mut("New key", ui);
+                                        if ui.button("Ok").clicked() {
+                                            edit = Some(MapEdit::Rename);
+                                        }
+                                        if ui.button("Cancel").clicked() {
+                                            edit = Some(MapEdit::CancelRename);
+                                        }
+                                    });
+                                }
+                            }
+                        });
+                    }
+
+                    // Entries can only be inserted if both their key and their value can be built
+                    if let (Some(new_key), Some(_)) = (K::default_factory(), V::default_factory()) {
+                        ui.horizontal(|ui| {
+                            state.new_key.get_or_insert_with(new_key).inspect_mut("New key", ui);
+                            insert = ui.button("Insert").clicked();
+                        });
+                    }
+
+                    if let Some(error) = &state.error {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                });
+
+                match edit {
+                    Some(MapEdit::Remove(index)) => {
+                        let mut position = 0;
+                        self.retain(|_, _| {
+                            position += 1;
+                            position - 1 != index
+                        });
+                        state.error = None;
+                    }
+                    Some(MapEdit::Rename) => {
+                        if let Some((original, renamed)) = state.renaming.take() {
+                            if original != renamed && self.contains_key(&renamed) {
+                                state.error = Some(format!("Key \"{}\" already exists", renamed.to_string()));
+                                state.renaming = Some((original, renamed));
+                            } else {
+                                if let Some(value) = self.remove(&original) {
+                                    self.insert(renamed, value);
+                                }
+                                state.error = None;
+                            }
+                        }
+                    }
+                    Some(MapEdit::CancelRename) => {
+                        state.renaming = None;
+                        state.error = None;
+                    }
+                    None => {}
+                }
+
+                if let (true, Some(new_value)) = (insert, V::default_factory()) {
+                    if let Some(new_key) = state.new_key.take() {
+                        if self.contains_key(&new_key) {
+                            state.error = Some(format!("Key \"{}\" already exists", new_key.to_string()));
+                            state.new_key = Some(new_key);
+                        } else {
+                            self.insert(new_key, new_value());
+                            state.error = None;
+                        }
+                    }
+                }
+            }
+
+            fn duplicate(&self) -> Option<Self> {
+                self.iter().map(|(key, value)| Some((key.duplicate()?, value.duplicate()?))).collect()
+            }
+
+            fn default_factory() -> Option<fn() -> Self> {
+                Some($map::new)
+            }
+        }
+    };
+}
+
+impl_inspect_map!(HashMap, Hash + Eq);
+impl_inspect_map!(BTreeMap, Ord);
+
+impl<T: crate::EguiInspect> crate::EguiInspect for Option<T> {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        match self {
+            Some(value) => value.inspect(label, ui),
+            None => {
+                ui.horizontal(|ui| {
+                    ui.label(label.to_owned() + ":");
+                    ui.weak("None");
+                });
+            }
+        }
+    }
+
+    /// The value can only be enabled if `T` has a default factory
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        inspect_mut_option(self, label, ui, T::default_factory());
+    }
+
+    fn duplicate(&self) -> Option<Self> {
+        match self {
+            Some(value) => value.duplicate().map(Some),
+            None => Some(None),
+        }
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(|| None)
+    }
+}
+
+impl<T: crate::EguiInspect> crate::InspectOption<T> for Option<T> {
+    fn inspect_mut_with_factory(&mut self, label: &str, ui: &mut Ui, factory: fn() -> T) {
+        inspect_mut_option(self, label, ui, Some(factory));
+    }
+}
+
+/// Checkbox enabling the value with `factory`, or only disabling it if there is no factory
+fn inspect_mut_option<T: crate::EguiInspect>(value: &mut Option<T>, label: &str, ui: &mut Ui, factory: Option<fn() -> T>) {
+    ui.horizontal_top(|ui| {
+        let mut enabled = value.is_some();
+        let response = ui
+            .add_enabled(enabled || factory.is_some(), egui::Checkbox::new(&mut enabled, ""))
+            .on_disabled_hover_text("There is no default value to enable it with");
+        if response.changed() {
+            *value = if enabled { factory.map(|factory| factory()) } else { None };
+        }
+
+        match value {
+            Some(value) => {
+                ui.vertical(|ui| value.inspect_mut(label, ui));
+            }
+            None => {
+                ui.label(label.to_owned() + ":");
+                ui.weak("None");
+            }
+        }
+    });
+}
+
+/// Label displayed in place of a value that can't be accessed right now
+fn inaccessible_value(label: &str, ui: &mut Ui, status: &str, reason: &str) {
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        ui.colored_label(ui.visuals().warn_fg_color, status)
+            .on_hover_text(reason);
+    });
+}
+
+/// Methods of `Box` forwarded to its content, for sized and `dyn EguiInspect` contents
+macro_rules! forward_box_inspect {
+    () => {
+        fn inspect(&self, label: &str, ui: &mut Ui) {
+            (**self).inspect(label, ui);
+        }
+
+        fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+            (**self).inspect_mut(label, ui);
+        }
+
+        fn inspect_shared(&self, label: &str, ui: &mut Ui) {
+            (**self).inspect_shared(label, ui);
+        }
+    };
+}
+
+impl<T: crate::EguiInspect> crate::EguiInspect for Box<T> {
+    forward_box_inspect!();
+
+    fn duplicate(&self) -> Option<Self> {
+        (**self).duplicate().map(Box::new)
+    }
+
+    fn default_factory() -> Option<fn() -> Self> {
+        default_factory_from!(T => Box::new(new_default::<T>()))
+    }
+}
+
+impl crate::EguiInspect for Box<dyn crate::EguiInspect> {
+    forward_box_inspect!();
+}
+
+macro_rules! impl_inspect_shared_pointer {
+    (@forward $pointer:ident) => {
+        fn inspect(&self, label: &str, ui: &mut Ui) {
+            (**self).inspect(label, ui);
+        }
+
+        fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+            if let Some(value) = $pointer::get_mut(self) {
+                value.inspect_mut(label, ui);
+                return;
+            }
+
+            ui.horizontal_top(|ui| {
+                ui.colored_label(ui.visuals().warn_fg_color, "shared")
+                    .on_hover_text(concat!("This ", stringify!($pointer), " is not unique, only interior mutability allows edition"));
+                ui.vertical(|ui| (**self).inspect_shared(label, ui));
+            });
+        }
+
+        fn inspect_shared(&self, label: &str, ui: &mut Ui) {
+            (**self).inspect_shared(label, ui);
+        }
+
+        /// Like `Clone`, the copy shares its content with the original
+        fn duplicate(&self) -> Option<Self> {
+            Some($pointer::clone(self))
+        }
+    };
+    ($($pointer:ident),+) => {
+        $(
+        impl<T: crate::EguiInspect> crate::EguiInspect for $pointer<T> {
+            impl_inspect_shared_pointer!(@forward $pointer);
+
+            fn default_factory() -> Option<fn() -> Self> {
+                default_factory_from!(T => $pointer::new(new_default::<T>()))
+            }
+        }
+
+        impl crate::EguiInspect for $pointer<dyn crate::EguiInspect> {
+            impl_inspect_shared_pointer!(@forward $pointer);
+        }
+        )*
+    };
+}
+
+impl_inspect_shared_pointer!(Rc, Arc);
+
+impl<T: crate::EguiInspect + Copy> crate::EguiInspect for Cell<T> {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        self.get().inspect(label, ui);
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        self.get_mut().inspect_mut(label, ui);
+    }
+
+    fn inspect_shared(&self, label: &str, ui: &mut Ui) {
+        let mut value = self.get();
+        value.inspect_mut(label, ui);
+        self.set(value);
+    }
+
+    fn duplicate(&self) -> Option<Self> {
+        Some(Cell::new(self.get()))
+    }
+
+    fn default_factory() -> Option<fn() -> Self> {
+        default_factory_from!(T => Cell::new(new_default::<T>()))
+    }
+}
+
+impl<T: crate::EguiInspect> crate::EguiInspect for RefCell<T> {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        match self.try_borrow() {
+            Ok(value) => value.inspect(label, ui),
+            Err(_) => inaccessible_value(label, ui, "borrowed", "This RefCell is currently mutably borrowed"),
+        }
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        self.get_mut().inspect_mut(label, ui);
+    }
+
+    fn inspect_shared(&self, label: &str, ui: &mut Ui) {
+        match self.try_borrow_mut() {
+            Ok(mut value) => value.inspect_mut(label, ui),
+            Err(_) => inaccessible_value(label, ui, "borrowed", "This RefCell is currently borrowed"),
+        }
+    }
+
+    /// `None` while the content is mutably borrowed
+    fn duplicate(&self) -> Option<Self> {
+        self.try_borrow().ok()?.duplicate().map(RefCell::new)
+    }
+
+    fn default_factory() -> Option<fn() -> Self> {
+        default_factory_from!(T => RefCell::new(new_default::<T>()))
+    }
+}
+
+impl<T: crate::EguiInspect> crate::EguiInspect for Mutex<T> {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        match self.try_lock() {
+            Ok(value) => value.inspect(label, ui),
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner().inspect(label, ui),
+            Err(TryLockError::WouldBlock) => inaccessible_value(label, ui, "locked", "This Mutex is currently locked"),
+        }
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        self.get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .inspect_mut(label, ui);
+    }
+
+    fn inspect_shared(&self, label: &str, ui: &mut Ui) {
+        match self.try_lock() {
+            Ok(mut value) => value.inspect_mut(label, ui),
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner().inspect_mut(label, ui),
+            Err(TryLockError::WouldBlock) => inaccessible_value(label, ui, "locked", "This Mutex is currently locked"),
+        }
+    }
+
+    /// `None` while the mutex is locked
+    fn duplicate(&self) -> Option<Self> {
+        let value = match self.try_lock() {
+            Ok(value) => value,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+        value.duplicate().map(Mutex::new)
+    }
+
+    fn default_factory() -> Option<fn() -> Self> {
+        default_factory_from!(T => Mutex::new(new_default::<T>()))
+    }
+}
+
+impl<T: crate::EguiInspect> crate::EguiInspect for RwLock<T> {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        match self.try_read() {
+            Ok(value) => value.inspect(label, ui),
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner().inspect(label, ui),
+            Err(TryLockError::WouldBlock) => inaccessible_value(label, ui, "locked", "This RwLock is currently locked for writing"),
+        }
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        self.get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .inspect_mut(label, ui);
+    }
+
+    fn inspect_shared(&self, label: &str, ui: &mut Ui) {
+        match self.try_write() {
+            Ok(mut value) => value.inspect_mut(label, ui),
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner().inspect_mut(label, ui),
+            Err(TryLockError::WouldBlock) => inaccessible_value(label, ui, "locked", "This RwLock is currently locked"),
+        }
+    }
+
+    /// `None` while the lock is held for writing
+    fn duplicate(&self) -> Option<Self> {
+        let value = match self.try_read() {
+            Ok(value) => value,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+        value.duplicate().map(RwLock::new)
+    }
+
+    fn default_factory() -> Option<fn() -> Self> {
+        default_factory_from!(T => RwLock::new(new_default::<T>()))
+    }
+}
+
+impl crate::EguiInspect for Color32 {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        show_color(label, ui, *self);
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            color_picker::color_edit_button_srgba(ui, self, color_picker::Alpha::OnlyBlend);
+        });
+    }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+impl crate::EguiInspect for Rgba {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        show_color(label, ui, *self);
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            color_picker::color_edit_button_rgba(ui, self, color_picker::Alpha::OnlyBlend);
+        });
+    }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+impl crate::EguiInspect for Hsva {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        show_color(label, ui, *self);
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            color_picker::color_edit_button_hsva(ui, self, color_picker::Alpha::OnlyBlend);
+        });
+    }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+fn show_color(label: &str, ui: &mut Ui, color: impl Into<Color32>) {
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        color_picker::show_color(ui, color, ui.spacing().interact_size);
+    });
+}
+
+/// Component of a color stored in an array or a tuple
+trait ColorComponent: Copy {
+    fn to_linear(self, space: ColorSpace) -> f32;
+    fn from_linear(linear: f32, space: ColorSpace) -> Self;
+    /// Alpha is never gamma encoded
+    fn to_alpha(self) -> f32;
+    fn from_alpha(alpha: f32) -> Self;
+}
+
+impl ColorComponent for f32 {
+    fn to_linear(self, space: ColorSpace) -> f32 {
+        match space {
+            ColorSpace::Srgb => ecolor::linear_from_gamma(self),
+            ColorSpace::Linear => self,
+        }
+    }
+    fn from_linear(linear: f32, space: ColorSpace) -> Self {
+        match space {
+            ColorSpace::Srgb => ecolor::gamma_from_linear(linear),
+            ColorSpace::Linear => linear,
+        }
+    }
+    fn to_alpha(self) -> f32 {
+        self
+    }
+    fn from_alpha(alpha: f32) -> Self {
+        alpha
+    }
+}
+
+impl ColorComponent for u8 {
+    fn to_linear(self, space: ColorSpace) -> f32 {
+        match space {
+            ColorSpace::Srgb => ecolor::linear_f32_from_gamma_u8(self),
+            ColorSpace::Linear => ecolor::linear_f32_from_linear_u8(self),
+        }
+    }
+    fn from_linear(linear: f32, space: ColorSpace) -> Self {
+        match space {
+            ColorSpace::Srgb => ecolor::gamma_u8_from_linear_f32(linear),
+            ColorSpace::Linear => ecolor::linear_u8_from_linear_f32(linear),
+        }
+    }
+    fn to_alpha(self) -> f32 {
+        ecolor::linear_f32_from_linear_u8(self)
+    }
+    fn from_alpha(alpha: f32) -> Self {
+        ecolor::linear_u8_from_linear_f32(alpha)
+    }
+}
+
+/// Linear and unmultiplied RGBA of the components, opaque unless alpha is enabled by `options`
+fn components_to_linear<C: ColorComponent>(components: &[C], options: ColorOptions) -> [f32; 4] {
+    let alpha = match components.get(3) {
+        Some(alpha) if options.alpha => alpha.to_alpha(),
+        _ => 1.0,
+    };
+    [
+        components[0].to_linear(options.space),
+        components[1].to_linear(options.space),
+        components[2].to_linear(options.space),
+        alpha,
+    ]
+}
+
+fn components_to_rgba<C: ColorComponent>(components: &[C], options: ColorOptions) -> Rgba {
+    let [r, g, b, a] = components_to_linear(components, options);
+    Rgba::from_rgba_unmultiplied(r, g, b, a)
+}
+
+/// Edit the first 3 components as RGB, and the fourth one as alpha if enabled by `options`.
+/// The color is edited as HSVA, which keeps the RGB components when alpha goes down to 0,
+/// unlike the premultiplied `Rgba`.
+fn inspect_mut_color_components<C: ColorComponent>(components: &mut [C], label: &str, ui: &mut Ui, options: ColorOptions) {
+    let edit_alpha = options.alpha && components.len() == 4;
+    let [r, g, b, a] = components_to_linear(components, options);
+    let mut hsva = Hsva::from_rgba_unmultiplied(r, g, b, a);
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        let alpha = if edit_alpha { color_picker::Alpha::OnlyBlend } else { color_picker::Alpha::Opaque };
+        if color_picker::color_edit_button_hsva(ui, &mut hsva, alpha).changed() {
+            let [r, g, b, a] = hsva.to_rgba_unmultiplied();
+            components[0] = C::from_linear(r, options.space);
+            components[1] = C::from_linear(g, options.space);
+            components[2] = C::from_linear(b, options.space);
+            if edit_alpha {
+                components[3] = C::from_alpha(a);
+            }
+        }
+    });
+}
+
+macro_rules! impl_inspect_color {
+    ($($t:ty),+) => {
+        $(
+        impl crate::InspectColor for [$t; 3] {
+            fn inspect_color(&self, label: &str, ui: &mut Ui, options: ColorOptions) {
+                show_color(label, ui, components_to_rgba(self, options));
+            }
+            fn inspect_mut_color(&mut self, label: &str, ui: &mut Ui, options: ColorOptions) {
+                inspect_mut_color_components(self, label, ui, options);
+            }
+        }
+
+        impl crate::InspectColor for [$t; 4] {
+            fn inspect_color(&self, label: &str, ui: &mut Ui, options: ColorOptions) {
+                show_color(label, ui, components_to_rgba(self, options));
+            }
+            fn inspect_mut_color(&mut self, label: &str, ui: &mut Ui, options: ColorOptions) {
+                inspect_mut_color_components(self, label, ui, options);
+            }
+        }
+
+        impl crate::InspectColor for ($t, $t, $t) {
+            fn inspect_color(&self, label: &str, ui: &mut Ui, options: ColorOptions) {
+                [self.0, self.1, self.2].inspect_color(label, ui, options);
+            }
+            fn inspect_mut_color(&mut self, label: &str, ui: &mut Ui, options: ColorOptions) {
+                let mut components = [self.0, self.1, self.2];
+                components.inspect_mut_color(label, ui, options);
+                [self.0, self.1, self.2] = components;
+            }
+        }
+
+        impl crate::InspectColor for ($t, $t, $t, $t) {
+            fn inspect_color(&self, label: &str, ui: &mut Ui, options: ColorOptions) {
+                [self.0, self.1, self.2, self.3].inspect_color(label, ui, options);
+            }
+            fn inspect_mut_color(&mut self, label: &str, ui: &mut Ui, options: ColorOptions) {
+                let mut components = [self.0, self.1, self.2, self.3];
+                components.inspect_mut_color(label, ui, options);
+                [self.0, self.1, self.2, self.3] = components;
+            }
+        }
+        )*
+    };
+}
+
+impl_inspect_color!(f32, u8);
+
+/// Named components displayed on a single row
+fn components_row(label: &str, ui: &mut Ui, components: &[(&str, f32)]) {
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        for (name, value) in components {
+            ui.label(format!("{}: {}", name, value));
+        }
+    });
+}
+
+/// Drag values of named components on a single row, clamped to `min` if given
+fn components_row_mut(label: &str, ui: &mut Ui, components: &mut [(&str, &mut f32)], min: Option<f32>) {
+    ui.horizontal(|ui| {
+        ui.label(label.to_owned() + ":");
+        for (name, value) in components {
+            let mut drag_value = egui::DragValue::new(*value).prefix(format!("{}: ", name)).speed(0.1);
+            if let Some(min) = min {
+                drag_value = drag_value.clamp_range(min..=f32::INFINITY);
+            }
+            ui.add(drag_value);
+        }
+    });
+}
+
+impl crate::EguiInspect for Vec2 {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        components_row(label, ui, &[("x", self.x), ("y", self.y)]);
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        components_row_mut(label, ui, &mut [("x", &mut self.x), ("y", &mut self.y)], None);
+    }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+impl crate::EguiInspect for Pos2 {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        components_row(label, ui, &[("x", self.x), ("y", self.y)]);
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        components_row_mut(label, ui, &mut [("x", &mut self.x), ("y", &mut self.y)], None);
+    }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+impl crate::EguiInspect for Rect {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        ui.label(label.to_owned() + ":");
+        ui.indent(label, |ui| {
+            self.min.inspect("min", ui);
+            self.max.inspect("max", ui);
+        });
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        ui.label(label.to_owned() + ":");
+        ui.indent(label, |ui| {
+            self.min.inspect_mut("min", ui);
+            self.max.inspect_mut("max", ui);
+        });
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+}
+
+impl crate::EguiInspect for Stroke {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            ui.label(format!("width: {}", self.width));
+            color_picker::show_color(ui, self.color, ui.spacing().interact_size);
+        });
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            ui.add(egui::DragValue::new(&mut self.width).prefix("width: ").speed(0.1).clamp_range(0.0..=f32::INFINITY));
+            color_picker::color_edit_button_srgba(ui, &mut self.color, color_picker::Alpha::OnlyBlend);
+        });
+    }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+impl crate::EguiInspect for Rounding {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        components_row(label, ui, &[("nw", self.nw), ("ne", self.ne), ("sw", self.sw), ("se", self.se)]);
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        components_row_mut(
+            label,
+            ui,
+            &mut [("nw", &mut self.nw), ("ne", &mut self.ne), ("sw", &mut self.sw), ("se", &mut self.se)],
+            Some(0.0),
+        );
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+impl crate::EguiInspect for Margin {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        components_row(
+            label,
+            ui,
+            &[("left", self.left), ("right", self.right), ("top", self.top), ("bottom", self.bottom)],
+        );
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        components_row_mut(
+            label,
+            ui,
+            &mut [
+                ("left", &mut self.left),
+                ("right", &mut self.right),
+                ("top", &mut self.top),
+                ("bottom", &mut self.bottom),
+            ],
+            None,
+        );
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+    fn default_factory() -> Option<fn() -> Self> {
+        Some(Self::default)
+    }
+}
+
+const ALIGNMENTS: [(Align2, &str); 9] = [
+    (Align2::LEFT_TOP, "Left top"),
+    (Align2::CENTER_TOP, "Center top"),
+    (Align2::RIGHT_TOP, "Right top"),
+    (Align2::LEFT_CENTER, "Left center"),
+    (Align2::CENTER_CENTER, "Center"),
+    (Align2::RIGHT_CENTER, "Right center"),
+    (Align2::LEFT_BOTTOM, "Left bottom"),
+    (Align2::CENTER_BOTTOM, "Center bottom"),
+    (Align2::RIGHT_BOTTOM, "Right bottom"),
+];
+
+fn alignment_name(align: Align2) -> &'static str {
+    ALIGNMENTS
+        .iter()
+        .find(|(alignment, _)| *alignment == align)
+        .map_or("", |(_, name)| name)
+}
+
+impl crate::EguiInspect for Align2 {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            ui.label(alignment_name(*self));
+        });
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            egui::ComboBox::from_id_source(label)
+                .selected_text(alignment_name(*self))
+                .show_ui(ui, |ui| {
+                    for (alignment, name) in ALIGNMENTS {
+                        ui.selectable_value(self, alignment, name);
+                    }
+                });
+        });
+    }
+    fn is_scalar() -> bool {
+        true
+    }
+    fn duplicate(&self) -> Option<Self> {
+        Some(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_integer, integer_digits, parse_integer, IntegerWidth};
+    use crate::IntegerFormat;
+
+    const FORMATS: [IntegerFormat; 4] = [
+        IntegerFormat::Decimal,
+        IntegerFormat::Hexadecimal,
+        IntegerFormat::Binary,
+        IntegerFormat::Octal,
+    ];
+
+    fn width(min: impl Into<f64>, max: impl Into<f64>) -> IntegerWidth {
+        IntegerWidth::new(min.into(), max.into())
+    }
+
+    #[test]
+    fn integer_width_of_the_type() {
+        assert_eq!(width(u8::MIN, u8::MAX), IntegerWidth { bits: 8, signed: false });
+        assert_eq!(width(i8::MIN, i8::MAX), IntegerWidth { bits: 8, signed: true });
+        assert_eq!(width(i32::MIN, i32::MAX), IntegerWidth { bits: 32, signed: true });
+        assert_eq!(IntegerWidth::new(u64::MIN as f64, u64::MAX as f64), IntegerWidth { bits: 64, signed: false });
+        assert_eq!(IntegerWidth::new(i64::MIN as f64, i64::MAX as f64), IntegerWidth { bits: 64, signed: true });
+        assert_eq!(IntegerWidth::new(i128::MIN as f64, i128::MAX as f64), IntegerWidth { bits: 128, signed: true });
+    }
+
+    #[test]
+    fn integer_digits_cover_the_type() {
+        // (width, decimal, hexadecimal, binary, octal)
+        let cases = [
+            (width(u8::MIN, u8::MAX), 1, 2, 8, 3),
+            (width(i8::MIN, i8::MAX), 1, 2, 8, 3),
+            (width(u16::MIN, u16::MAX), 1, 4, 16, 6),
+            (width(u32::MIN, u32::MAX), 1, 8, 32, 11),
+            (width(i32::MIN, i32::MAX), 1, 8, 32, 11),
+            (IntegerWidth::new(u64::MIN as f64, u64::MAX as f64), 1, 16, 64, 22),
+            (IntegerWidth::new(i64::MIN as f64, i64::MAX as f64), 1, 16, 64, 22),
+        ];
+        for (width, decimal, hexadecimal, binary, octal) in cases {
+            assert_eq!(integer_digits(width, IntegerFormat::Decimal), decimal);
+            assert_eq!(integer_digits(width, IntegerFormat::Hexadecimal), hexadecimal);
+            assert_eq!(integer_digits(width, IntegerFormat::Binary), binary);
+            assert_eq!(integer_digits(width, IntegerFormat::Octal), octal);
+        }
+    }
+
+    #[test]
+    fn format_integer_pads_to_the_width() {
+        let byte = width(u8::MIN, u8::MAX);
+        assert_eq!(format_integer(5, IntegerFormat::Decimal, byte), "5");
+        assert_eq!(format_integer(5, IntegerFormat::Hexadecimal, byte), "0x05");
+        assert_eq!(format_integer(5, IntegerFormat::Binary, byte), "0b00000101");
+        assert_eq!(format_integer(5, IntegerFormat::Octal, byte), "0o005");
+        let word = IntegerWidth::new(u64::MIN as f64, u64::MAX as f64);
+        assert_eq!(format_integer(u64::MAX as i128, IntegerFormat::Hexadecimal, word), "0xffffffffffffffff");
+    }
+
+    #[test]
+    fn signed_integers_are_formatted_in_twos_complement() {
+        let byte = width(i8::MIN, i8::MAX);
+        assert_eq!(format_integer(-1, IntegerFormat::Decimal, byte), "-1");
+        assert_eq!(format_integer(-1, IntegerFormat::Hexadecimal, byte), "0xff");
+        assert_eq!(format_integer(-1, IntegerFormat::Binary, byte), "0b11111111");
+        assert_eq!(format_integer(-1, IntegerFormat::Octal, byte), "0o377");
+        assert_eq!(format_integer(-128, IntegerFormat::Hexadecimal, byte), "0x80");
+        let word = IntegerWidth::new(i64::MIN as f64, i64::MAX as f64);
+        assert_eq!(format_integer(i64::MIN as i128, IntegerFormat::Hexadecimal, word), "0x8000000000000000");
+    }
+
+    #[test]
+    fn integers_round_trip() {
+        let cases = [
+            (width(i8::MIN, i8::MAX), vec![0, 1, -1, i8::MIN as i128, i8::MAX as i128]),
+            (width(u8::MIN, u8::MAX), vec![0, 1, u8::MAX as i128]),
+            (width(i32::MIN, i32::MAX), vec![-1, i32::MIN as i128, i32::MAX as i128]),
+            (IntegerWidth::new(i64::MIN as f64, i64::MAX as f64), vec![-1, i64::MIN as i128, i64::MAX as i128]),
+            (IntegerWidth::new(u64::MIN as f64, u64::MAX as f64), vec![0, u64::MAX as i128]),
+            (IntegerWidth::new(i128::MIN as f64, i128::MAX as f64), vec![-1, i128::MIN, i128::MAX]),
+        ];
+        for (width, values) in cases {
+            for format in FORMATS {
+                for &value in &values {
+                    let text = format_integer(value, format, width);
+                    assert_eq!(parse_integer(&text, format, width), Some(value), "{} in {:?}", text, format);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_integer_accepts_missing_prefix_and_signs() {
+        let byte = width(u8::MIN, u8::MAX);
+        let signed_byte = width(i8::MIN, i8::MAX);
+        assert_eq!(parse_integer(" ff ", IntegerFormat::Hexadecimal, byte), Some(255));
+        assert_eq!(parse_integer("ff", IntegerFormat::Hexadecimal, signed_byte), Some(-1));
+        assert_eq!(parse_integer("-101", IntegerFormat::Binary, signed_byte), Some(-5));
+        assert_eq!(parse_integer("0o17", IntegerFormat::Octal, byte), Some(15));
+        // Out of the width, left for the widget to clamp
+        assert_eq!(parse_integer("0x1ff", IntegerFormat::Hexadecimal, signed_byte), Some(511));
+        assert_eq!(parse_integer("0x1g", IntegerFormat::Hexadecimal, byte), None);
+        assert_eq!(parse_integer("12", IntegerFormat::Binary, byte), None);
+        assert_eq!(parse_integer("", IntegerFormat::Decimal, byte), None);
     }
 }
@@ -0,0 +1,371 @@
+//! Small arithmetic expression language used to type numbers.
+//!
+//! Supports `+ - * / % ^`, parentheses, the constants `pi`, `tau` and `e`,
+//! the current value as `x`, common functions like `sqrt(2)` or `min(x, 10)`,
+//! integer literals in hexadecimal (`0x1f`), binary (`0b101`) and octal (`0o17`),
+//! and relative edits of the current value: `+= 0.5`, `-= 1`, `*= 2`, `/= 3`.
+
+/// Evaluate `text`, where `current` is the value being edited
+pub(crate) fn evaluate(text: &str, current: f64) -> Result<f64, String> {
+    let text = text.trim();
+    let (operator, text) = match text.get(..2) {
+        Some(prefix @ ("+=" | "-=" | "*=" | "/=")) => (prefix.chars().next(), &text[2..]),
+        _ => (None, text),
+    };
+
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        position: 0,
+        current,
+    };
+    let value = parser.expression()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("Unexpected {}", token));
+    }
+
+    let value = match operator {
+        Some('+') => current + value,
+        Some('-') => current - value,
+        Some('*') => current * value,
+        Some('/') => current / value,
+        _ => value,
+    };
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(String::from("The result is not a finite number"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Operator(char),
+    Open,
+    Close,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "number `{}`", number),
+            Token::Ident(ident) => write!(f, "`{}`", ident),
+            Token::Operator(operator) => write!(f, "`{}`", operator),
+            Token::Open => write!(f, "`(`"),
+            Token::Close => write!(f, "`)`"),
+            Token::Comma => write!(f, "`,`"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(index, c)) = chars.peek() {
+                // Accept exponents like `1e-3`, but not in hexadecimal literals like `0x1e-3`
+                let literal = &text[start..index];
+                let exponent_sign = (c == '-' || c == '+')
+                    && literal.ends_with(['e', 'E'])
+                    && !literal.starts_with("0x")
+                    && !literal.starts_with("0X");
+                if c.is_ascii_alphanumeric() || c == '.' || c == '_' || exponent_sign {
+                    end = index + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Number(parse_number(&text[start..end])?));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(index, c)) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' {
+                    end = index + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Ident(text[start..end].to_lowercase()));
+        } else {
+            tokens.push(match c {
+                '+' | '-' | '*' | '/' | '%' | '^' => Token::Operator(c),
+                '(' => Token::Open,
+                ')' => Token::Close,
+                ',' => Token::Comma,
+                _ => return Err(format!("Unexpected character `{}`", c)),
+            });
+            chars.next();
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_number(text: &str) -> Result<f64, String> {
+    let digits = text.replace('_', "");
+    let radix = match digits.get(..2) {
+        Some("0x" | "0X") => Some(16),
+        Some("0b" | "0B") => Some(2),
+        Some("0o" | "0O") => Some(8),
+        _ => None,
+    };
+    let parsed = match radix {
+        Some(radix) => i128::from_str_radix(&digits[2..], radix).map(|n| n as f64).ok(),
+        None => digits.parse::<f64>().ok(),
+    };
+    parsed.ok_or_else(|| format!("Invalid number `{}`", text))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    current: f64,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {}, found {}", expected, token)),
+            None => Err(format!("Expected {}", expected)),
+        }
+    }
+
+    /// `term (('+' | '-') term)*`
+    fn expression(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        while let Some(Token::Operator(operator @ ('+' | '-'))) = self.peek().cloned() {
+            self.next();
+            let rhs = self.term()?;
+            value = if operator == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    /// `unary (('*' | '/' | '%') unary)*`
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        while let Some(Token::Operator(operator @ ('*' | '/' | '%'))) = self.peek().cloned() {
+            self.next();
+            let rhs = self.unary()?;
+            value = match operator {
+                '*' => value * rhs,
+                '/' => value / rhs,
+                _ => value % rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    /// `('-' | '+') unary | power`
+    fn unary(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some(Token::Operator('-')) => {
+                self.next();
+                Ok(-self.unary()?)
+            }
+            Some(Token::Operator('+')) => {
+                self.next();
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// `atom ('^' unary)?`, right associative
+    fn power(&mut self) -> Result<f64, String> {
+        let base = self.atom()?;
+        if let Some(Token::Operator('^')) = self.peek() {
+            self.next();
+            return Ok(base.powf(self.unary()?));
+        }
+        Ok(base)
+    }
+
+    /// `number | '(' expression ')' | constant | function '(' arguments ')'`
+    fn atom(&mut self) -> Result<f64, String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(number),
+            Some(Token::Open) => {
+                let value = self.expression()?;
+                self.expect(Token::Close)?;
+                Ok(value)
+            }
+            Some(Token::Ident(ident)) if self.peek() == Some(&Token::Open) => {
+                self.next();
+                let mut arguments = vec![self.expression()?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.next();
+                    arguments.push(self.expression()?);
+                }
+                self.expect(Token::Close)?;
+                call(&ident, &arguments)
+            }
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "pi" => Ok(std::f64::consts::PI),
+                "tau" => Ok(std::f64::consts::TAU),
+                "e" => Ok(std::f64::consts::E),
+                "x" => Ok(self.current),
+                _ => Err(format!("Unknown constant `{}`", ident)),
+            },
+            Some(token) => Err(format!("Unexpected {}", token)),
+            None => Err(String::from("Unexpected end of expression")),
+        }
+    }
+}
+
+fn call(function: &str, arguments: &[f64]) -> Result<f64, String> {
+    let unary: Option<fn(f64) -> f64> = match function {
+        "sqrt" => Some(f64::sqrt),
+        "abs" => Some(f64::abs),
+        "floor" => Some(f64::floor),
+        "ceil" => Some(f64::ceil),
+        "round" => Some(f64::round),
+        "sin" => Some(f64::sin),
+        "cos" => Some(f64::cos),
+        "tan" => Some(f64::tan),
+        "asin" => Some(f64::asin),
+        "acos" => Some(f64::acos),
+        "atan" => Some(f64::atan),
+        "exp" => Some(f64::exp),
+        "ln" => Some(f64::ln),
+        "log10" => Some(f64::log10),
+        "log2" => Some(f64::log2),
+        "deg" => Some(f64::to_degrees),
+        "rad" => Some(f64::to_radians),
+        _ => None,
+    };
+    if let Some(unary) = unary {
+        return match arguments {
+            [argument] => Ok(unary(*argument)),
+            _ => Err(format!("`{}` takes 1 argument", function)),
+        };
+    }
+
+    let binary: Option<fn(f64, f64) -> f64> = match function {
+        "min" => Some(f64::min),
+        "max" => Some(f64::max),
+        "pow" => Some(f64::powf),
+        "atan2" => Some(f64::atan2),
+        _ => None,
+    };
+    match (binary, arguments) {
+        (Some(binary), [lhs, rhs]) => Ok(binary(*lhs, *rhs)),
+        (Some(_), _) => Err(format!("`{}` takes 2 arguments", function)),
+        (None, _) => Err(format!("Unknown function `{}`", function)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::evaluate;
+
+    fn eval(text: &str) -> f64 {
+        evaluate(text, 10.0).unwrap()
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("10 - 4 - 3"), 3.0);
+        assert_eq!(eval("12 / 3 / 2"), 2.0);
+        assert_eq!(eval("7 % 4 * 2"), 6.0);
+        assert_eq!(eval("2 * 3 ^ 2"), 18.0);
+    }
+
+    #[test]
+    fn power_is_right_associative() {
+        assert_eq!(eval("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(eval("2 ^ -1"), 0.5);
+    }
+
+    #[test]
+    fn unary_minus_binds_looser_than_power() {
+        assert_eq!(eval("-2 ^ 2"), -4.0);
+        assert_eq!(eval("(-2) ^ 2"), 4.0);
+        assert_eq!(eval("--3"), 3.0);
+        assert_eq!(eval("+3"), 3.0);
+    }
+
+    #[test]
+    fn relative_edits() {
+        assert_eq!(eval("+= 0.5"), 10.5);
+        assert_eq!(eval("-= 1"), 9.0);
+        assert_eq!(eval("*= 2 + 1"), 30.0);
+        assert_eq!(eval("/= 4"), 2.5);
+        assert_eq!(eval("x * 2"), 20.0);
+    }
+
+    #[test]
+    fn radix_literals() {
+        assert_eq!(eval("0x1f"), 31.0);
+        assert_eq!(eval("0XFF"), 255.0);
+        assert_eq!(eval("0b101"), 5.0);
+        assert_eq!(eval("0o17"), 15.0);
+        assert_eq!(eval("1_000"), 1000.0);
+        assert_eq!(eval("0b1111_0000"), 240.0);
+        assert!(evaluate("0b102", 0.0).is_err());
+        assert!(evaluate("0x", 0.0).is_err());
+    }
+
+    #[test]
+    fn exponents() {
+        assert_eq!(eval("1e3"), 1000.0);
+        assert_eq!(eval("1e-3"), 0.001);
+        assert_eq!(eval("2.5E+2"), 250.0);
+        // `e` is a hexadecimal digit, so the sign is a subtraction
+        assert_eq!(eval("0x1e-3"), 27.0);
+        assert!(evaluate("2e", 0.0).unwrap_err().contains("Invalid number `2e`"));
+    }
+
+    #[test]
+    fn constants_and_functions() {
+        assert_eq!(eval("2 * pi"), std::f64::consts::TAU);
+        assert_eq!(eval("TAU"), std::f64::consts::TAU);
+        assert_eq!(eval("sqrt(16) + abs(-2)"), 6.0);
+        assert_eq!(eval("min(x, 3)"), 3.0);
+        assert_eq!(eval("max(1, pow(2, 3))"), 8.0);
+        assert_eq!(eval("deg(pi)"), 180.0);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(evaluate("foo", 0.0).unwrap_err().contains("Unknown constant `foo`"));
+        assert!(evaluate("foo(1)", 0.0).unwrap_err().contains("Unknown function `foo`"));
+        assert!(evaluate("sqrt(1, 2)", 0.0).unwrap_err().contains("takes 1 argument"));
+        assert!(evaluate("min(1)", 0.0).unwrap_err().contains("takes 2 arguments"));
+        assert!(evaluate("1 +", 0.0).is_err());
+        assert!(evaluate("(1 + 2", 0.0).is_err());
+        assert!(evaluate("1 2", 0.0).is_err());
+        assert!(evaluate("1 $ 2", 0.0).is_err());
+        assert!(evaluate("", 0.0).is_err());
+    }
+
+    #[test]
+    fn division_by_zero_is_an_error() {
+        assert!(evaluate("1 / 0", 0.0).is_err());
+        assert!(evaluate("0 / 0", 0.0).is_err());
+        assert!(evaluate("/= 0", 1.0).is_err());
+        assert!(evaluate("sqrt(-1)", 0.0).is_err());
+    }
+}
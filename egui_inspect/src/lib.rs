@@ -5,7 +5,7 @@
 //! Basic usage would be
 //! ```
 //! # use egui_inspect::*;
-//! #[derive(EguiInspect)]
+//! #[derive(EguiInspect, Default)]
 //! struct MyApp {
 //!     #[inspect(no_edit)]
 //!     string: String,
@@ -30,13 +30,35 @@
 //!    boolean.inspect(label, ui);
 //! }
 //!
-//! fn main() {
+//! fn show_app(ui: &mut egui::Ui) {
 //!     let app = MyApp::default();
-//!     app.inspect("My App", &ui); // here `ui` would be some `&mut egui::Ui`
+//!     app.inspect("My App", ui);
 //! }
 //! ```
 //!
-//! You can add attributes to structures field.
+//! Enums can be derived too. They are displayed as a combo box of their variants
+//! when inspected mutably, and as the name of the current variant otherwise.
+//! The fields of the active variant are then inspected like struct fields, with the same attributes.
+//!
+//! When selecting another variant, its fields are built with `Default`, unless the variant
+//! has a `default` attribute.
+//! ```
+//! # use egui_inspect::*;
+//! #[derive(EguiInspect)]
+//! enum Mode {
+//!     Idle,
+//!     #[inspect(name = "Running fast")]
+//!     Running { #[inspect(min = 1.0, max = 10.0)] speed: f32 },
+//!     #[inspect(default = "default_target")]
+//!     Targeting(String),
+//! }
+//!
+//! fn default_target() -> Mode {
+//!     Mode::Targeting("Player".to_owned())
+//! }
+//! ```
+//!
+//! You can add attributes to structures field, named or not.
 //! Currently supported attributes are defined in the struct AttributeArgs of egui_inspect_derive
 //!
 //! Here is a list of supported attributes.
@@ -45,14 +67,56 @@
 //! - `name` *(String)*: Use custom label for the given field instead of the internal field name
 //! - `hide` *(bool)*: If true, doesn't generate code for the given field
 //! - `no_edit` *(bool)*: If true, never call mut function for the given field (May be overridden by other params)
-//! - `slider` *(bool)*: If true, use a slider when inspecting numbers (`mut` only)
-//! - `min` *(f32)*: Min value for inspecting numbers (`mut` only)
-//! - `max` *(f32)*: Max value for inspecting numbers (`mut` only)
+//! - `slider` *(bool)*: If true, use a slider when inspecting numbers, implied by `min`, `max`, `range`, `logarithmic`,
+//!   `clamp` and `smart_aim` (`mut` only).
+//!   Otherwise numbers use a drag value, except `u8` which uses a slider over its whole range
+//! - `min` *(expression)*: Min value for inspecting numbers, of the number's type (`mut` only)
+//! - `max` *(expression)*: Max value for inspecting numbers, of the number's type (`mut` only)
+//! - `range` *(expression)*: Inclusive range of values for inspecting numbers, like `0..=u64::MAX` (`mut` only)
+//! - `speed` *(expression)*: How much a number changes when dragged, per point (`mut` only)
+//! - `step` *(expression)*: Round numbers to a multiple of `step` (`mut` only)
+//! - `decimals` *(usize)*: Number of decimals displayed for numbers (`mut` only, except with `angle` and `percent`)
+//! - `prefix` *(String)*: Text displayed before numbers (`mut` only, except with `angle` and `percent`)
+//! - `suffix` *(String)*: Text displayed after numbers, like a unit (`mut` only, except with `angle` and `percent`)
+//! - `logarithmic` *(bool)*: If true, sliders use a logarithmic scale (`mut` only)
+//! - `clamp` *(always, never or edits)*: When sliders clamp numbers to their range, `edits` by default (`mut` only)
+//! - `smart_aim` *(bool)*: If false, sliders don't prefer round values when dragged (`mut` only)
+//! - `format` *(hex, binary, octal or decimal)*: Base used to display and edit integers
+//! - `bits` *(bool or list of String)*: Display integers as a row of bit toggles, optionally only the named bits
+//! - `expr` *(bool)*: Edit numbers in a text box evaluating math expressions like `1/60`, `2*pi` or `+=0.5`, see also [set_expression_input] (`mut` only)
+//! - `angle` *(bool)*: Display a float stored in radians as degrees, bounds being given in degrees
+//! - `percent` *(bool)*: Display a float stored as a `0..=1` factor as a percentage, bounds being given in percents
+//! - `color` *(bool or list of srgb, linear and alpha)*: Display `[f32; 3]`, `[f32; 4]`, `[u8; 3]`, `[u8; 4]` and
+//!   matching tuples as a color, with sRGB (default) or linear components, editing the alpha component if `alpha` is given
+//! - `inline` *(bool)*: Display the elements of arrays and vectors on a single row, as done by default for arrays
+//!   of up to 4 scalars
 //! - `multiline` *(bool)*: If true, display the text on multiple lines (`mut` only)
 //! - `custom_func` *(String)*: Use custom function for non-mut inspect (Evaluate the string as a function path)
 //! - `custom_func_mut` *(String)*: Use custom function for mut inspect (Evaluate the string as a function path)
+//! - `factory` *(String)*: Use custom function to build the value when enabling an `Option` (Evaluate the string as a function path)
+//! - `new_item` *(String)*: Use custom function to build the items added to a `Vec`, which otherwise requires
+//!   the items to implement `Default` (Evaluate the string as a function path)
+//! - `item_label` *(String)*: Label the items of a `Vec` or an array with one of their fields (`"name"`)
+//!   instead of their index
+//! - `item_label_fn` *(String)*: Label the items of a `Vec` or an array with a function taking a reference
+//!   to the item and returning something displayable (Evaluate the string as a function path)
+//!   Items labelled by either attribute keep their state when reordered, so their labels should be unique
+//!
+//! Structs and enums themselves support the following attributes, written like `#[inspect(default, clone)]`:
+//!
+//! - `default` *(bool)*: Build new values with `Default::default`, see [EguiInspect::default_factory]
+//! - `clone` *(bool)*: Duplicate values with `Clone::clone`, see [EguiInspect::duplicate]
+//!
+//! Both are detected without the attribute for types without generic parameters.
+//!
+//! Enum variants support the following attributes:
+//!
+//! - `name` *(String)*: Use custom label for the given variant instead of the variant ident
+//! - `default` *(String)*: Use custom function to build the variant when it gets selected (Evaluate the string as a function path)
 //!
 
+use std::ops::RangeInclusive;
+
 /// See also [EguiInspect]
 pub use egui_inspect_derive::*;
 
@@ -60,11 +124,203 @@ pub use egui_inspect_derive::*;
 pub trait EguiInspect {
     fn inspect(&self, label: &str, ui: &mut egui::Ui);
     fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui);
+    /// Inspect a value only reachable through a shared reference, like the content of a shared `Rc`.
+    /// Types with interior mutability (`RefCell`, `Mutex`, ...) override it to remain editable,
+    /// every other type is only displayed.
+    fn inspect_shared(&self, label: &str, ui: &mut egui::Ui) {
+        self.inspect(label, ui);
+    }
+    /// Whether the type is displayed as a single short widget, like numbers.
+    /// Containers use it to lay their items out on a single row.
+    fn is_scalar() -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+    /// Copy of the value, used by collections to duplicate their items.
+    /// `None` if the type can't be cloned, which is the default.
+    /// Derived implementations return a clone when the type implements `Clone`.
+    /// This can't be detected for generic types, which need the `#[inspect(clone)]` container attribute.
+    /// Hand-written implementations must override it, usually with `Some(self.clone())`,
+    /// for a `Vec` of the type to offer duplicating items.
+    fn duplicate(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+    /// Function building new values, used by collections to add items.
+    /// `None` if the type has no sensible default value, which is the default.
+    /// Derived implementations use `Default::default` when the type implements `Default`.
+    /// This can't be detected for generic types, which need the `#[inspect(default)]` container attribute.
+    /// Hand-written implementations must override it, usually with `Some(Self::default)`,
+    /// for a `Vec` of the type to offer adding items.
+    fn default_factory() -> Option<fn() -> Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// When a slider clamps its value to its range
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SliderClamping {
+    /// The value is always kept in range, even if it has been set out of range elsewhere
+    Always,
+    /// The value is never clamped, the range only drives the slider
+    Never,
+    /// Only the values entered through the slider are clamped
+    #[default]
+    Edits,
+}
+
+/// Base used to display and edit integers.
+/// Signed integers are written in two's complement in other bases than decimal, like `0xff` for `-1i8`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IntegerFormat {
+    #[default]
+    Decimal,
+    /// Displayed as `0x1f`
+    Hexadecimal,
+    /// Displayed as `0b00011111`
+    Binary,
+    /// Displayed as `0o037`
+    Octal,
+}
+
+/// Presentation options for the widgets of [InspectNumber]
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberOptions {
+    /// How much the value changes when dragged, per point (drag value only)
+    pub speed: Option<f64>,
+    /// Values are rounded to a multiple of `step`
+    pub step: Option<f64>,
+    /// Number of decimals displayed
+    pub decimals: Option<usize>,
+    /// Text displayed before the value
+    pub prefix: String,
+    /// Text displayed after the value, like a unit
+    pub suffix: String,
+    /// Use a logarithmic scale (slider only)
+    pub logarithmic: bool,
+    /// When the value is clamped to the range (slider only)
+    pub clamp: SliderClamping,
+    /// Prefer round values when dragging (slider only)
+    pub smart_aim: bool,
+    /// Base used to display and edit the value (integers only)
+    pub format: IntegerFormat,
+    /// Edit the value in a text box evaluating math expressions, like `2 * pi` or `+= 0.5`
+    pub expression: bool,
+}
+
+impl Default for NumberOptions {
+    fn default() -> Self {
+        Self {
+            speed: None,
+            step: None,
+            decimals: None,
+            prefix: String::new(),
+            suffix: String::new(),
+            logarithmic: false,
+            clamp: SliderClamping::default(),
+            smart_aim: true,
+            format: IntegerFormat::default(),
+            expression: false,
+        }
+    }
+}
+
+/// Edit every number of the given context in a text box evaluating math expressions,
+/// as if they all had the `expr` attribute
+pub fn set_expression_input(ctx: &egui::Context, enabled: bool) {
+    ctx.data().insert_temp(expression_input_id(), enabled);
+}
+
+/// Whether [set_expression_input] is enabled for the given context
+pub fn expression_input(ctx: &egui::Context) -> bool {
+    ctx.data().get_temp(expression_input_id()).unwrap_or(false)
+}
+
+fn expression_input_id() -> egui::Id {
+    egui::Id::new("egui_inspect::expression_input")
+}
+
+/// Number of items displayed per page by default in large collections
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Set how many items of large collections are displayed per page in the given context,
+/// until changed for a specific collection
+pub fn set_page_size(ctx: &egui::Context, page_size: usize) {
+    ctx.data().insert_temp(page_size_id(), page_size.max(1));
+}
+
+/// Number of items displayed per page in the given context, see [set_page_size]
+pub fn page_size(ctx: &egui::Context) -> usize {
+    ctx.data().get_temp(page_size_id()).unwrap_or(DEFAULT_PAGE_SIZE)
+}
+
+fn page_size_id() -> egui::Id {
+    egui::Id::new("egui_inspect::page_size")
 }
 
 pub trait InspectNumber {
     fn inspect_with_slider(&mut self, label: &str, ui: &mut egui::Ui, min: f32, max: f32);
+    /// Same as [InspectNumber::inspect_with_slider], with bounds of the number's own type
+    fn inspect_with_range(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        range: RangeInclusive<Self>,
+        options: &NumberOptions,
+    ) where
+        Self: Sized;
     fn inspect_with_drag_value(&mut self, label: &str, ui: &mut egui::Ui);
+    /// Same as [InspectNumber::inspect_with_drag_value], customized by `options`
+    fn inspect_with_drag_value_options(&mut self, label: &str, ui: &mut egui::Ui, options: &NumberOptions);
+}
+
+pub trait InspectInteger {
+    /// Same as [EguiInspect::inspect], displaying the value in the given base
+    fn inspect_with_format(&self, label: &str, ui: &mut egui::Ui, format: IntegerFormat);
+    /// Display one toggle per bit. When `names` is not empty, only the named bits are displayed,
+    /// starting from the least significant one.
+    fn inspect_bits(&self, label: &str, ui: &mut egui::Ui, names: &[&str]);
+    /// Same as [InspectInteger::inspect_bits], allowing to flip bits
+    fn inspect_mut_bits(&mut self, label: &str, ui: &mut egui::Ui, names: &[&str]);
+}
+
+/// How the components of a color are stored
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Gamma encoded components, as used by `Color32` and most image formats
+    #[default]
+    Srgb,
+    /// Linear components, as commonly used by shaders
+    Linear,
+}
+
+/// Options of the color picker of [InspectColor]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ColorOptions {
+    pub space: ColorSpace,
+    /// Whether the fourth component is an alpha to be edited, otherwise it is left untouched
+    pub alpha: bool,
+}
+
+/// Inspect RGB or RGBA components as a color
+pub trait InspectColor {
+    fn inspect_color(&self, label: &str, ui: &mut egui::Ui, options: ColorOptions);
+    fn inspect_mut_color(&mut self, label: &str, ui: &mut egui::Ui, options: ColorOptions);
+}
+
+/// Inspect the elements of a collection on a single row, labelled x, y, z and w
+/// for up to 4 elements, or by index otherwise
+pub trait InspectInline {
+    fn inspect_inline(&self, label: &str, ui: &mut egui::Ui);
+    /// Same as [InspectInline::inspect_inline], without adding nor removing elements
+    fn inspect_mut_inline(&mut self, label: &str, ui: &mut egui::Ui);
 }
 
 pub trait InspectString {
@@ -72,4 +328,87 @@ pub trait InspectString {
     fn inspect_mut_singleline(&mut self, label: &str, ui: &mut egui::Ui);
 }
 
+/// Options of the collections implementing [InspectCollection]
+pub struct CollectionOptions<T> {
+    /// Build the items added to the collection, [EguiInspect::default_factory] being used if `None`
+    pub new_item: Option<fn() -> T>,
+    /// Label of each item, its index like `[0]` being used if `None`.
+    /// Items are then scoped under their label instead of their index, so that their state follows them
+    /// when reordered: labels should be unique.
+    pub item_label: Option<fn(&T) -> String>,
+}
+
+impl<T> Default for CollectionOptions<T> {
+    fn default() -> Self {
+        Self {
+            new_item: None,
+            item_label: None,
+        }
+    }
+}
+
+pub trait InspectCollection<T> {
+    /// Same as [EguiInspect::inspect], customized by `options`
+    fn inspect_with_options(&self, label: &str, ui: &mut egui::Ui, options: &CollectionOptions<T>);
+    /// Same as [EguiInspect::inspect_mut], customized by `options`
+    fn inspect_mut_with_options(&mut self, label: &str, ui: &mut egui::Ui, options: &CollectionOptions<T>);
+}
+
+pub trait InspectOption<T> {
+    /// Same as [EguiInspect::inspect_mut], but build the value with `factory` when enabling it
+    fn inspect_mut_with_factory(&mut self, label: &str, ui: &mut egui::Ui, factory: fn() -> T);
+}
+
 pub mod base_type_inspect;
+mod expression;
+
+/// Support code for the derive macro, not part of the public API
+#[doc(hidden)]
+pub mod __private {
+    /// Autoref specialization: `(&&Probe(value)).duplicate_probe()` resolves to [DuplicateClone]
+    /// when the type implements `Clone`, and to [DuplicateFallback] otherwise.
+    pub struct Probe<'a, T>(pub &'a T);
+
+    pub trait DuplicateClone<T> {
+        fn duplicate_probe(&self) -> Option<T>;
+    }
+
+    impl<T: Clone> DuplicateClone<T> for &Probe<'_, T> {
+        fn duplicate_probe(&self) -> Option<T> {
+            Some(self.0.clone())
+        }
+    }
+
+    pub trait DuplicateFallback<T> {
+        fn duplicate_probe(&self) -> Option<T>;
+    }
+
+    impl<T> DuplicateFallback<T> for Probe<'_, T> {
+        fn duplicate_probe(&self) -> Option<T> {
+            None
+        }
+    }
+
+    /// Same as [Probe] for `Default`, through [FactoryDefault] and [FactoryFallback]
+    pub struct TypeProbe<T>(pub std::marker::PhantomData<T>);
+
+    pub trait FactoryDefault<T> {
+        fn factory_probe(&self) -> Option<fn() -> T>;
+    }
+
+    impl<T: Default> FactoryDefault<T> for &TypeProbe<T> {
+        fn factory_probe(&self) -> Option<fn() -> T> {
+            Some(T::default)
+        }
+    }
+
+    pub trait FactoryFallback<T> {
+        fn factory_probe(&self) -> Option<fn() -> T>;
+    }
+
+    impl<T> FactoryFallback<T> for TypeProbe<T> {
+        fn factory_probe(&self) -> Option<fn() -> T> {
+            None
+        }
+    }
+}
@@ -1,12 +1,13 @@
-use crate::utils::get_path_str;
-use crate::AttributeArgs;
+use crate::utils::{get_path_str, quote_option, typed_number_bound};
+use crate::{AttributeArgs, IntegerFormat, SliderClamping};
 use proc_macro2::TokenStream;
-use quote::quote_spanned;
+use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
-use syn::Field;
+use darling::FromMeta;
+use syn::{parse_quote, Expr, Field, GenericArgument, PathArguments, RangeLimits, Type};
 
 pub(crate) fn path_is_internally_handled(path_str: &String) -> bool {
-    return path_str == "f32"
+    path_str == "f32"
         || path_str == "f64"
         || path_str == "u8"
         || path_str == "i8"
@@ -20,75 +21,240 @@ pub(crate) fn path_is_internally_handled(path_str: &String) -> bool {
         || path_str == "isize"
         || path_str == "bool"
         || path_str == "String"
-        || path_str == "str";
+        || path_str == "str"
+        || path_str == "Option"
+        || path_str == "Vec"
 }
 
 pub(crate) fn try_handle_internal_path(
     field: &Field,
+    place: &TokenStream,
+    name_str: &str,
     mutable: bool,
     attrs: &AttributeArgs,
 ) -> Option<TokenStream> {
-    let path_str = get_path_str(&field.ty);
-
-    if path_str.is_none() {
-        return None;
+    if let Type::Array(array) = &field.ty {
+        return handle_collection(field, place, name_str, mutable, attrs, &array.elem);
     }
-    let path_str = path_str.unwrap();
+
+    let path_str = get_path_str(&field.ty)?;
 
     if !path_is_internally_handled(&path_str) {
         return None;
     }
 
     match path_str.as_str() {
-        "f64" | "f32" | "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64" => {
-            handle_number_path(&field, mutable, &attrs)
+        "f64" | "f32" | "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64" | "usize"
+        | "isize" => {
+            handle_number_path(field, place, name_str, &path_str, mutable, attrs)
         }
-        "String" => handle_string_path(&field, mutable, &attrs),
+        "String" => handle_string_path(field, place, name_str, mutable, attrs),
+        "Option" => handle_option_path(field, place, name_str, mutable, attrs),
+        "Vec" => handle_vec_path(field, place, name_str, mutable, attrs),
         _ => None,
     }
 }
 
-fn handle_number_path(field: &Field, mutable: bool, attrs: &AttributeArgs) -> Option<TokenStream> {
-    let name = &field.ident;
-
-    let name_str = match &attrs.name {
-        Some(n) => n.clone(),
-        None => name.clone().unwrap().to_string(),
-    };
+fn handle_number_path(
+    field: &Field,
+    place: &TokenStream,
+    name_str: &str,
+    path_str: &str,
+    mutable: bool,
+    attrs: &AttributeArgs,
+) -> Option<TokenStream> {
+    // Giving any bound or slider mode implies a slider, and `u8` uses one over its whole range by default
+    let slider = attrs.slider
+        || attrs.min.is_some()
+        || attrs.max.is_some()
+        || attrs.range.is_some()
+        || attrs.logarithmic
+        || attrs.clamp.is_some()
+        || attrs.smart_aim.is_some()
+        || path_str == "u8";
+    let options = get_number_options(attrs);
+    let is_float = path_str == "f32" || path_str == "f64";
 
-    let no_edit = attrs.no_edit;
-    let slider = attrs.slider;
-    let min = attrs.min;
-    let max = attrs.max;
+    if is_float && (attrs.format.is_some() || attrs.bits.is_some()) {
+        panic!("`format` and `bits` can only be used on integers");
+    }
 
-    if no_edit {
-        return None;
+    if let Some(bits) = &attrs.bits {
+        let names = &bits.0;
+        return Some(if mutable {
+            quote_spanned! {field.span() => {
+                egui_inspect::InspectInteger::inspect_mut_bits(&mut #place, &#name_str, ui, &[#(#names),*]);
+                }
+            }
+        } else {
+            quote_spanned! {field.span() => {
+                egui_inspect::InspectInteger::inspect_bits(&#place, &#name_str, ui, &[#(#names),*]);
+                }
+            }
+        });
     }
 
-    if mutable && slider {
+    if !mutable && attrs.format.is_some() {
+        let format = get_integer_format(attrs.format);
         return Some(quote_spanned! {field.span() => {
-        egui_inspect::InspectNumber::inspect_with_slider(&mut self.#name, &#name_str, ui, #min, #max);
+            egui_inspect::InspectInteger::inspect_with_format(&#place, &#name_str, ui, #format);
             }
         });
     }
-    if mutable && !slider {
+
+    // Angles and percentages are edited through a converted copy, written back only on change
+    let (value, to_display, from_display) = match (attrs.angle, attrs.percent) {
+        (false, false) => (place.clone(), None, None),
+        (true, true) => panic!("`angle` and `percent` can't be used together"),
+        _ if !is_float => panic!("`angle` and `percent` can only be used on floats"),
+        (true, false) => (
+            quote!(__inspect_value),
+            Some(quote!(#place.to_degrees())),
+            Some(quote!(__inspect_value.to_radians())),
+        ),
+        (false, true) => (
+            quote!(__inspect_value),
+            Some(quote!(#place * 100.0)),
+            Some(quote!(__inspect_value / 100.0)),
+        ),
+    };
+
+    let widget = if mutable && slider {
+        let (min, max) = get_number_bounds(attrs, path_str);
+        let min = typed_number_bound(&min, path_str);
+        let max = typed_number_bound(&max, path_str);
+        quote! {
+            egui_inspect::InspectNumber::inspect_with_range(&mut #value, &#name_str, ui, (#min)..=(#max), &#options);
+        }
+    } else if mutable {
+        quote! {
+            egui_inspect::InspectNumber::inspect_with_drag_value_options(&mut #value, &#name_str, ui, &#options);
+        }
+    } else if let Some(to_display) = &to_display {
+        let prefix = attrs.prefix.clone().unwrap_or_default();
+        let suffix = get_number_suffix(attrs);
+        // Rounded like a drag value, so that conversions don't show float noise like `45.000004`
+        let decimals = match attrs.decimals {
+            Some(decimals) => quote!(#decimals..=#decimals),
+            None => quote!(0..=6),
+        };
         return Some(quote_spanned! {field.span() => {
-            egui_inspect::InspectNumber::inspect_with_drag_value(&mut self.#name, &#name_str, ui);
+            ui.horizontal(|ui| {
+                ui.label(::std::format!("{}:", #name_str));
+                let __inspect_text = egui::emath::format_with_decimals_in_range((#to_display) as f64, #decimals);
+                ui.label(::std::format!("{}{}{}", #prefix, __inspect_text, #suffix));
+            });
             }
         });
+    } else {
+        return None;
+    };
+
+    match (to_display, from_display) {
+        (Some(to_display), Some(from_display)) => Some(quote_spanned! {field.span() => {
+            let mut __inspect_value = #to_display;
+            let __inspect_previous = __inspect_value;
+            #widget
+            if __inspect_value != __inspect_previous {
+                #place = #from_display;
+            }
+            }
+        }),
+        _ => Some(quote_spanned! {field.span() => {
+            #widget
+            }
+        }),
     }
+}
 
-    return None;
+/// Suffix of the number, which defaults to the unit of `angle` and `percent`
+fn get_number_suffix(attrs: &AttributeArgs) -> String {
+    attrs.suffix.clone().unwrap_or_else(|| match (attrs.angle, attrs.percent) {
+        (true, _) => String::from("°"),
+        (_, true) => String::from("%"),
+        _ => String::new(),
+    })
 }
 
-fn handle_string_path(field: &Field, mutable: bool, attrs: &AttributeArgs) -> Option<TokenStream> {
-    let name = &field.ident;
+fn get_number_options(attrs: &AttributeArgs) -> TokenStream {
+    let speed = quote_option(&attrs.speed.as_ref().map(|speed| quote!((#speed) as f64)));
+    let step = quote_option(&attrs.step.as_ref().map(|step| quote!((#step) as f64)));
+    let decimals = quote_option(&attrs.decimals);
+    let prefix = attrs.prefix.clone().unwrap_or_default();
+    let suffix = get_number_suffix(attrs);
+    let logarithmic = attrs.logarithmic;
+    let clamp = match attrs.clamp {
+        Some(SliderClamping::Always) => quote!(egui_inspect::SliderClamping::Always),
+        Some(SliderClamping::Never) => quote!(egui_inspect::SliderClamping::Never),
+        Some(SliderClamping::Edits) | None => quote!(egui_inspect::SliderClamping::Edits),
+    };
+    let smart_aim = attrs.smart_aim.unwrap_or(true);
+    let format = get_integer_format(attrs.format);
+    let expression = attrs.expr;
+
+    quote! {
+        egui_inspect::NumberOptions {
+            speed: #speed,
+            step: #step,
+            decimals: #decimals,
+            prefix: ::std::string::String::from(#prefix),
+            suffix: ::std::string::String::from(#suffix),
+            logarithmic: #logarithmic,
+            clamp: #clamp,
+            smart_aim: #smart_aim,
+            format: #format,
+            expression: #expression,
+        }
+    }
+}
 
-    let name_str = match &attrs.name {
-        Some(n) => n.clone(),
-        None => name.clone().unwrap().to_string(),
+fn get_integer_format(format: Option<IntegerFormat>) -> TokenStream {
+    match format {
+        Some(IntegerFormat::Hexadecimal) => quote!(egui_inspect::IntegerFormat::Hexadecimal),
+        Some(IntegerFormat::Binary) => quote!(egui_inspect::IntegerFormat::Binary),
+        Some(IntegerFormat::Octal) => quote!(egui_inspect::IntegerFormat::Octal),
+        Some(IntegerFormat::Decimal) | None => quote!(egui_inspect::IntegerFormat::Decimal),
+    }
+}
+
+/// Bounds of the slider, from either the `range` attribute or `min` and `max`.
+/// Missing bounds span the whole type for `u8`, a full turn for angles, and `0..=100` otherwise.
+fn get_number_bounds(attrs: &AttributeArgs, path_str: &str) -> (Expr, Expr) {
+    let (default_min, default_max): (Expr, Expr) = match path_str {
+        "u8" => (parse_quote!(u8::MIN), parse_quote!(u8::MAX)),
+        _ if attrs.angle => (parse_quote!(0), parse_quote!(360)),
+        _ => (parse_quote!(0), parse_quote!(100)),
+    };
+
+    let Some(range) = &attrs.range else {
+        return (
+            attrs.min.clone().unwrap_or(default_min),
+            attrs.max.clone().unwrap_or(default_max),
+        );
     };
 
+    if attrs.min.is_some() || attrs.max.is_some() {
+        panic!("`range` can't be used along with `min` or `max`");
+    }
+    let Expr::Range(range) = range else {
+        panic!("`range` must be a range expression, like `0..=10`");
+    };
+    if !matches!(range.limits, RangeLimits::Closed(_)) {
+        panic!("`range` must be an inclusive range, like `0..=10`");
+    }
+    (
+        range.start.as_deref().cloned().unwrap_or(default_min),
+        range.end.as_deref().cloned().unwrap_or(default_max),
+    )
+}
+
+fn handle_string_path(
+    field: &Field,
+    place: &TokenStream,
+    name_str: &str,
+    mutable: bool,
+    attrs: &AttributeArgs,
+) -> Option<TokenStream> {
     let multiline = attrs.multiline;
     let no_edit = attrs.no_edit;
 
@@ -98,16 +264,118 @@ fn handle_string_path(field: &Field, mutable: bool, attrs: &AttributeArgs) -> Op
 
     if mutable && multiline {
         return Some(quote_spanned! {field.span() => {
-        egui_inspect::InspectString::inspect_mut_multiline(&mut self.#name, &#name_str, ui);
+        egui_inspect::InspectString::inspect_mut_multiline(&mut #place, &#name_str, ui);
             }
         });
     }
     if mutable && !multiline {
         return Some(quote_spanned! {field.span() => {
-        egui_inspect::InspectString::inspect_mut_singleline(&mut self.#name, &#name_str, ui);
+        egui_inspect::InspectString::inspect_mut_singleline(&mut #place, &#name_str, ui);
             }
         });
     }
 
-    return None;
+    None
+}
+
+fn handle_option_path(
+    field: &Field,
+    place: &TokenStream,
+    name_str: &str,
+    mutable: bool,
+    attrs: &AttributeArgs,
+) -> Option<TokenStream> {
+    let factory = attrs.factory.as_ref()?;
+
+    if !mutable {
+        return None;
+    }
+
+    let factory = syn::Path::from_string(factory)
+        .unwrap_or_else(|_| panic!("Could not find function: {}", factory));
+    Some(quote_spanned! {field.span() => {
+        egui_inspect::InspectOption::inspect_mut_with_factory(&mut #place, &#name_str, ui, #factory);
+        }
+    })
+}
+
+fn handle_vec_path(
+    field: &Field,
+    place: &TokenStream,
+    name_str: &str,
+    mutable: bool,
+    attrs: &AttributeArgs,
+) -> Option<TokenStream> {
+    let Type::Path(type_path) = &field.ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(arguments) = &type_path.path.segments.last()?.arguments else {
+        return None;
+    };
+    let Some(GenericArgument::Type(item_type)) = arguments.args.first() else {
+        return None;
+    };
+    handle_collection(field, place, name_str, mutable, attrs, item_type)
+}
+
+/// `Vec` and arrays with a `new_item`, `item_label` or `item_label_fn` attribute
+fn handle_collection(
+    field: &Field,
+    place: &TokenStream,
+    name_str: &str,
+    mutable: bool,
+    attrs: &AttributeArgs,
+    item_type: &Type,
+) -> Option<TokenStream> {
+    if attrs.new_item.is_none() && attrs.item_label.is_none() && attrs.item_label_fn.is_none() {
+        return None;
+    }
+    if attrs.new_item.is_some() && matches!(field.ty, Type::Array(_)) {
+        panic!("`new_item` can't be used on arrays, which have a fixed length");
+    }
+
+    let new_item = quote_option(&attrs.new_item.as_ref().map(|new_item| {
+        syn::Path::from_string(new_item).unwrap_or_else(|_| panic!("Could not find function: {}", new_item))
+    }));
+    let item_label = quote_option(&get_item_label(attrs, item_type));
+    let options = quote! {
+        egui_inspect::CollectionOptions::<#item_type> {
+            new_item: #new_item,
+            item_label: #item_label,
+        }
+    };
+
+    Some(if mutable {
+        quote_spanned! {field.span() => {
+            egui_inspect::InspectCollection::inspect_mut_with_options(&mut #place, &#name_str, ui, &#options);
+            }
+        }
+    } else {
+        quote_spanned! {field.span() => {
+            egui_inspect::InspectCollection::inspect_with_options(&#place, &#name_str, ui, &#options);
+            }
+        }
+    })
+}
+
+/// Closure labelling the items, from the field of `item_label` or the function of `item_label_fn`
+fn get_item_label(attrs: &AttributeArgs, item_type: &Type) -> Option<TokenStream> {
+    match (&attrs.item_label, &attrs.item_label_fn) {
+        (Some(_), Some(_)) => panic!("`item_label` and `item_label_fn` can't be used together"),
+        (Some(field), None) => {
+            let field = syn::parse_str::<syn::Member>(field)
+                .unwrap_or_else(|_| panic!("`item_label` must be a field of the items: {}", field));
+            Some(quote! {
+                |item: &#item_type| ::std::string::ToString::to_string(&item.#field)
+            })
+        }
+        (None, Some(function)) => {
+            let path = syn::Path::from_string(function)
+                .unwrap_or_else(|_| panic!("Could not find function: {}", function));
+            Some(quote! {
+                |item: &#item_type| ::std::string::ToString::to_string(&#path(item))
+            })
+        }
+        (None, None) => None,
+    }
 }
@@ -1,17 +1,17 @@
 use proc_macro2::{Ident, TokenStream};
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned};
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, parse_quote, Data, DeriveInput, Field, Fields, FieldsNamed, GenericParam,
-    Generics, Index,
+    parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Expr, Field, Fields,
+    FieldsNamed, FieldsUnnamed, GenericParam, Generics, Index, Variant,
 };
 
-use darling::{FromField, FromMeta};
+use darling::{FromDeriveInput, FromField, FromMeta, FromVariant};
 
 mod internal_paths;
 mod utils;
 
-#[derive(Debug, FromField)]
+#[derive(Debug, Default, FromField)]
 #[darling(attributes(inspect), default)]
 struct AttributeArgs {
     /// Name of the field to be displayed on UI labels
@@ -20,45 +20,225 @@ struct AttributeArgs {
     hide: bool,
     /// Doesn't call mut function for the given field (May be overridden by other params)
     no_edit: bool,
-    /// Use slider function for numbers
+    /// Use slider function for numbers, implied by `min`, `max`, `range`, `logarithmic`, `clamp` and `smart_aim`
     slider: bool,
     /// Min value for numbers
-    min: f32,
+    min: Option<Expr>,
     /// Max value for numbers
-    max: f32,
+    max: Option<Expr>,
+    /// Range of values for numbers, replacing `min` and `max`
+    range: Option<Expr>,
+    /// Drag speed for numbers
+    speed: Option<Expr>,
+    /// Round numbers to a multiple of this step
+    step: Option<Expr>,
+    /// Number of decimals displayed for numbers
+    decimals: Option<usize>,
+    /// Text displayed before numbers
+    prefix: Option<String>,
+    /// Text displayed after numbers
+    suffix: Option<String>,
+    /// Use a logarithmic scale for number sliders
+    logarithmic: bool,
+    /// When number sliders clamp the value to their range
+    clamp: Option<SliderClamping>,
+    /// Prefer round values when dragging number sliders
+    smart_aim: Option<bool>,
+    /// Base used to display and edit integers
+    format: Option<IntegerFormat>,
+    /// Display integers as bit toggles, optionally naming each bit starting from the least significant one
+    bits: Option<BitNames>,
+    /// Edit numbers in a text box evaluating math expressions
+    expr: bool,
+    /// Display a float stored in radians as degrees
+    angle: bool,
+    /// Display a float stored as a `0..=1` factor as a percentage
+    percent: bool,
+    /// Display RGB or RGBA components as a color: `color`, `color(linear)`, `color(srgb, alpha)`
+    color: Option<ColorArgs>,
+    /// Display the elements of arrays and vectors on a single row
+    inline: bool,
     /// Display mut text on multiple line
     multiline: bool,
     /// Use custom function for non-mut inspect
     custom_func: Option<String>,
     /// Use custom function for mut inspect
     custom_func_mut: Option<String>,
+    /// Use custom function to build the value when enabling an `Option`
+    factory: Option<String>,
+    /// Use custom function to build the items added to a `Vec`
+    new_item: Option<String>,
+    /// Label the items of a collection with one of their fields
+    item_label: Option<String>,
+    /// Label the items of a collection with a function taking the item
+    item_label_fn: Option<String>,
 }
 
-impl Default for AttributeArgs {
-    fn default() -> Self {
-        Self {
-            name: None,
-            hide: false,
-            no_edit: false,
-            slider: true,
-            min: 0.0,
-            max: 100.0,
-            multiline: false,
-            custom_func: None,
-            custom_func_mut: None,
+/// Mirror of `egui_inspect::SliderClamping`, written as a bare identifier: `clamp = always`
+#[derive(Debug, Clone, Copy)]
+enum SliderClamping {
+    Always,
+    Never,
+    Edits,
+}
+
+impl FromMeta for SliderClamping {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        match expr {
+            Expr::Path(path) => match path.path.get_ident() {
+                Some(ident) => Self::from_string(&ident.to_string()),
+                None => Err(darling::Error::unexpected_expr_type(expr)),
+            },
+            Expr::Lit(lit) => Self::from_value(&lit.lit),
+            _ => Err(darling::Error::unexpected_expr_type(expr)),
+        }
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            "edits" => Ok(Self::Edits),
+            _ => Err(darling::Error::unknown_value(value)),
         }
     }
 }
 
+/// Mirror of `egui_inspect::IntegerFormat`, written as a bare identifier: `format = hex`
+#[derive(Debug, Clone, Copy)]
+enum IntegerFormat {
+    Decimal,
+    Hexadecimal,
+    Binary,
+    Octal,
+}
+
+impl FromMeta for IntegerFormat {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        match expr {
+            Expr::Path(path) => match path.path.get_ident() {
+                Some(ident) => Self::from_string(&ident.to_string()),
+                None => Err(darling::Error::unexpected_expr_type(expr)),
+            },
+            Expr::Lit(lit) => Self::from_value(&lit.lit),
+            _ => Err(darling::Error::unexpected_expr_type(expr)),
+        }
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "decimal" | "dec" => Ok(Self::Decimal),
+            "hexadecimal" | "hex" => Ok(Self::Hexadecimal),
+            "binary" | "bin" => Ok(Self::Binary),
+            "octal" | "oct" => Ok(Self::Octal),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+/// Names given to the `bits` attribute: either `bits` alone or `bits = ["a", "b"]`
+#[derive(Debug, Default)]
+struct BitNames(Vec<syn::LitStr>);
+
+impl FromMeta for BitNames {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::default())
+    }
+
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        Vec::<syn::LitStr>::from_expr(expr).map(Self)
+    }
+}
+
+/// Options of the `color` attribute, mirroring `egui_inspect::ColorOptions`
+#[derive(Debug, Default)]
+struct ColorArgs {
+    linear: bool,
+    alpha: bool,
+}
+
+impl FromMeta for ColorArgs {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::default())
+    }
+
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        let mut args = Self::default();
+        for item in items {
+            let darling::ast::NestedMeta::Meta(syn::Meta::Path(path)) = item else {
+                return Err(darling::Error::unsupported_format("expression").with_span(item));
+            };
+            match path.get_ident().map(|ident| ident.to_string()).as_deref() {
+                Some("srgb") => args.linear = false,
+                Some("linear") => args.linear = true,
+                Some("alpha") => args.alpha = true,
+                _ => return Err(darling::Error::unknown_field_path(path)),
+            }
+        }
+        Ok(args)
+    }
+}
+
+#[derive(Debug, Default, FromVariant)]
+#[darling(attributes(inspect), default)]
+struct VariantArgs {
+    /// Name of the variant to be displayed on UI labels
+    name: Option<String>,
+    /// Use custom function to build the variant when it gets selected (Evaluate the string as a function path)
+    default: Option<String>,
+}
+
+#[derive(Debug, Default, FromDeriveInput)]
+#[darling(attributes(inspect), default)]
+struct ContainerArgs {
+    /// Derive `default_factory` as `Default::default`, bounding the impl on `Self: Default`
+    default: bool,
+    /// Derive `duplicate` as a clone, bounding the impl on `Self: Clone`
+    clone: bool,
+}
+
 #[proc_macro_derive(EguiInspect, attributes(inspect))]
 pub fn derive_egui_inspect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    if let Data::Union(ref data) = input.data {
+        return syn::Error::new_spanned(data.union_token, "EguiInspect cannot be derived for unions")
+            .to_compile_error()
+            .into();
+    }
+
+    let container = match ContainerArgs::from_derive_input(&input) {
+        Ok(container) => container,
+        Err(err) => return err.write_errors().into(),
+    };
+
     let name = input.ident;
 
     let generics = add_trait_bounds(input.generics);
+    let generics = add_variant_default_bounds(generics, &input.data);
+    let generics = add_container_bounds(generics, &name, &container);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let duplicate = if container.clone {
+        quote!(::std::option::Option::Some(::core::clone::Clone::clone(self)))
+    } else {
+        quote! {
+            #[allow(unused_imports)]
+            use egui_inspect::__private::{DuplicateClone as _, DuplicateFallback as _};
+            (&&egui_inspect::__private::Probe(self)).duplicate_probe()
+        }
+    };
+
+    let default_factory = if container.default {
+        quote!(::std::option::Option::Some(<Self as ::core::default::Default>::default))
+    } else {
+        quote! {
+            #[allow(unused_imports)]
+            use egui_inspect::__private::{FactoryDefault as _, FactoryFallback as _};
+            (&&egui_inspect::__private::TypeProbe::<Self>(::std::marker::PhantomData)).factory_probe()
+        }
+    };
+
     let inspect = inspect_struct(&input.data, &name, false);
 
     let inspect_mut = inspect_struct(&input.data, &name, true);
@@ -66,10 +246,20 @@ pub fn derive_egui_inspect(input: proc_macro::TokenStream) -> proc_macro::TokenS
     let expanded = quote! {
         impl #impl_generics egui_inspect::EguiInspect for #name #ty_generics #where_clause {
             fn inspect(&self, label: &str, ui: &mut egui::Ui) {
-                #inspect
+                ui.push_id(label, |ui| {
+                    #inspect
+                });
             }
             fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-                #inspect_mut
+                ui.push_id(label, |ui| {
+                    #inspect_mut
+                });
+            }
+            fn duplicate(&self) -> ::std::option::Option<Self> {
+                #duplicate
+            }
+            fn default_factory() -> ::std::option::Option<fn() -> Self> {
+                #default_factory
             }
         }
     };
@@ -88,50 +278,225 @@ fn add_trait_bounds(mut generics: Generics) -> Generics {
     generics
 }
 
+/// Fields of a variant are built with `Default` when it gets selected,
+/// unless the variant provides its own constructor
+fn add_variant_default_bounds(mut generics: Generics, data: &Data) -> Generics {
+    let Data::Enum(ref data) = *data else {
+        return generics;
+    };
+    if generics.type_params().next().is_none() {
+        return generics;
+    }
+
+    let where_clause = generics.make_where_clause();
+    for variant in data.variants.iter() {
+        let attr = VariantArgs::from_variant(variant).expect("Could not get attributes from variant");
+        if attr.default.is_some() {
+            continue;
+        }
+        for field in variant.fields.iter() {
+            let ty = &field.ty;
+            where_clause
+                .predicates
+                .push(parse_quote!(#ty: ::core::default::Default));
+        }
+    }
+    generics
+}
+
+/// The `default` and `clone` container attributes require the type itself to implement the trait,
+/// which for generic types depends on their parameters
+fn add_container_bounds(mut generics: Generics, name: &Ident, container: &ContainerArgs) -> Generics {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let self_ty = quote!(#name #ty_generics);
+
+    let where_clause = generics.make_where_clause();
+    if container.default {
+        where_clause
+            .predicates
+            .push(parse_quote!(#self_ty: ::core::default::Default));
+    }
+    if container.clone {
+        where_clause
+            .predicates
+            .push(parse_quote!(#self_ty: ::core::clone::Clone));
+    }
+    generics
+}
+
 fn inspect_struct(data: &Data, _struct_name: &Ident, mutable: bool) -> TokenStream {
     match *data {
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => handle_named_fields(fields, mutable),
-            Fields::Unnamed(ref fields) => {
-                let mut recurse = Vec::new();
-                for (i,_) in fields.unnamed.iter().enumerate() {
-                    let tuple_index = Index::from(i);
-                    let name = format!("Field {i}");
-                    let ref_str = if mutable { quote!(&mut) } else { quote!(&) };
-                    recurse.push(quote! { egui_inspect::EguiInspect::inspect(#ref_str self.#tuple_index, #name, ui);});
-                };
-
-                let result = quote! {
-                    ui.strong(label);
-                    #(#recurse)*
-                };
-                result
-            }
-            _ => unimplemented!("Unit cannot be inspected !")
+            Fields::Unnamed(ref fields) => handle_unnamed_fields(fields, mutable),
+            Fields::Unit => quote!(ui.label(label);),
         },
-        Data::Enum(_) | Data::Union(_) => unimplemented!("Enums and Unions are not yet supported"),
+        Data::Enum(ref data) => inspect_enum(data, mutable),
+        Data::Union(_) => unreachable!("Unions are rejected by derive_egui_inspect"),
     }
 }
 
-fn handle_named_fields(fields: &FieldsNamed, mutable: bool) -> TokenStream {
-    let recurse = fields.named.iter().map(|f| {
-        let attr = AttributeArgs::from_field(f).expect("Could not get attributes from field");
+fn inspect_enum(data: &DataEnum, mutable: bool) -> TokenStream {
+    if data.variants.is_empty() {
+        return quote!(match *self {});
+    }
+
+    let mut variant_names = Vec::new();
+    let mut variant_selections = Vec::new();
+    let mut variant_fields = Vec::new();
+    let mut has_fields = false;
+    for variant in data.variants.iter() {
+        let attr = VariantArgs::from_variant(variant).expect("Could not get attributes from variant");
+        let ident = &variant.ident;
+        let name_str = match &attr.name {
+            Some(n) => n.clone(),
+            None => ident.to_string(),
+        };
+
+        let pattern = match variant.fields {
+            Fields::Named(_) => quote!(Self::#ident { .. }),
+            Fields::Unnamed(_) => quote!(Self::#ident(..)),
+            Fields::Unit => quote!(Self::#ident),
+        };
+        let constructor = variant_constructor(variant, &attr);
+
+        variant_names.push(quote_spanned! { variant.span() => #pattern => #name_str, });
+        variant_selections.push(quote_spanned! { variant.span() =>
+            if ui.selectable_label(matches!(self, #pattern), #name_str).clicked() && !matches!(self, #pattern) {
+                *self = #constructor;
+            }
+        });
+        match handle_variant_fields(variant, mutable) {
+            Some(ts) => {
+                has_fields = true;
+                variant_fields.push(ts);
+            }
+            None => variant_fields.push(quote!(#pattern => {})),
+        }
+    }
+
+    let selected = quote! {
+        let selected = match self {
+            #(#variant_names)*
+        };
+    };
+
+    let fields = if has_fields {
+        quote! {
+            match self {
+                #(#variant_fields)*
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    if !mutable {
+        return quote! {
+            #selected
+            ui.horizontal(|ui| {
+                ui.label(label.to_owned() + ":");
+                ui.label(selected);
+            });
+            #fields
+        };
+    }
 
+    quote! {
+        #selected
+        ui.horizontal(|ui| {
+            ui.label(label.to_owned() + ":");
+            egui::ComboBox::from_id_source(label)
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    #(#variant_selections)*
+                });
+        });
+        #fields
+    }
+}
+
+/// Expression building the given variant when it gets selected in the combo box
+fn variant_constructor(variant: &Variant, attrs: &VariantArgs) -> TokenStream {
+    let ident = &variant.ident;
+
+    if let Some(default) = &attrs.default {
+        let path = syn::Path::from_string(default)
+            .unwrap_or_else(|_| panic!("Could not find function: {}", default));
+        return quote_spanned!(variant.span() => #path());
+    }
+
+    match variant.fields {
+        Fields::Named(ref fields) => {
+            let names = fields.named.iter().map(|f| &f.ident);
+            quote_spanned!(variant.span() => Self::#ident { #(#names: ::core::default::Default::default()),* })
+        }
+        Fields::Unnamed(ref fields) => {
+            let defaults = fields.unnamed.iter().map(|_| quote!(::core::default::Default::default()));
+            quote_spanned!(variant.span() => Self::#ident(#(#defaults),*))
+        }
+        Fields::Unit => quote_spanned!(variant.span() => Self::#ident),
+    }
+}
+
+/// Match arm inspecting the fields of the given variant, if it has any
+fn handle_variant_fields(variant: &Variant, mutable: bool) -> Option<TokenStream> {
+    let ident = &variant.ident;
+
+    let mut bindings = Vec::new();
+    let mut recurse = Vec::new();
+    for (i, f) in variant.fields.iter().enumerate() {
+        let attr = AttributeArgs::from_field(f).expect("Could not get attributes from field");
         if attr.hide {
-            return quote!();
+            bindings.push(quote!(_));
+            continue;
         }
 
-        let mutable = mutable && !attr.no_edit;
+        let binding = format_ident!("__inspect_field_{}", i);
+        let default_name = match &f.ident {
+            Some(name) => name.to_string(),
+            None => i.to_string(),
+        };
+        let id_source = format!("{}::{}", ident, default_name);
+        recurse.push(handle_field(f, &quote!((*#binding)), default_name, &id_source, mutable));
+        bindings.push(quote!(#binding));
+    }
+
+    if recurse.is_empty() {
+        return None;
+    }
 
-        if let Some(ts) = handle_custom_func(&f, mutable, &attr) {
-            return ts;
+    let pattern = match variant.fields {
+        Fields::Named(ref fields) => {
+            let names = fields.named.iter().map(|f| &f.ident);
+            quote!(Self::#ident { #(#names: #bindings),* })
         }
+        _ => quote!(Self::#ident(#(#bindings),*)),
+    };
 
-        if let Some(ts) = internal_paths::try_handle_internal_path(&f, mutable, &attr) {
-            return ts;
+    Some(quote_spanned! { variant.span() =>
+        #pattern => {
+            #(#recurse)*
         }
+    })
+}
+
+fn handle_named_fields(fields: &FieldsNamed, mutable: bool) -> TokenStream {
+    let recurse = fields.named.iter().map(|f| {
+        let name = &f.ident;
+        let name_str = name.clone().unwrap().to_string();
+        handle_field(f, &quote!(self.#name), name_str.clone(), &name_str, mutable)
+    });
+    quote! {
+        ui.strong(label);
+        #(#recurse)*
+    }
+}
 
-        return utils::get_default_function_call(&f, mutable, &attr);
+fn handle_unnamed_fields(fields: &FieldsUnnamed, mutable: bool) -> TokenStream {
+    let recurse = fields.unnamed.iter().enumerate().map(|(i, f)| {
+        let tuple_index = Index::from(i);
+        handle_field(f, &quote!(self.#tuple_index), i.to_string(), &i.to_string(), mutable)
     });
     quote! {
         ui.strong(label);
@@ -139,33 +504,146 @@ fn handle_named_fields(fields: &FieldsNamed, mutable: bool) -> TokenStream {
     }
 }
 
-fn handle_custom_func(field: &Field, mutable: bool, attrs: &AttributeArgs) -> Option<TokenStream> {
-    let name = &field.ident;
+/// Generate the code inspecting a single field, accessed through the place expression `place`.
+///
+/// The widgets are scoped under `id_source`, within the scope of the label of the whole value,
+/// so that their egui IDs follow the path of the field instead of its label.
+fn handle_field(
+    field: &Field,
+    place: &TokenStream,
+    default_name: String,
+    id_source: &str,
+    mutable: bool,
+) -> TokenStream {
+    let attr = AttributeArgs::from_field(field).expect("Could not get attributes from field");
+
+    if attr.hide {
+        return quote!();
+    }
+
+    let inspect = handle_field_widgets(field, place, default_name, mutable, &attr);
+    quote_spanned! { field.span() =>
+        ui.push_id(#id_source, |ui| {
+            #inspect
+        });
+    }
+}
+
+/// Pick the widgets for a field from its attributes and its type
+fn handle_field_widgets(
+    field: &Field,
+    place: &TokenStream,
+    default_name: String,
+    mutable: bool,
+    attr: &AttributeArgs,
+) -> TokenStream {
+    let mutable = mutable && !attr.no_edit;
 
-    let name_str = match &attrs.name {
+    let name_str = match &attr.name {
         Some(n) => n.clone(),
-        None => name.clone().unwrap().to_string(),
+        None => default_name,
     };
 
-    if mutable && !attrs.no_edit && attrs.custom_func_mut.is_some() {
-        let custom_func_mut = attrs.custom_func_mut.as_ref().unwrap();
-        let ident = syn::Path::from_string(custom_func_mut)
-            .expect(format!("Could not find function: {}", custom_func_mut).as_str());
-        return Some(quote_spanned! { field.span() => {
-                #ident(&mut self.#name, &#name_str, ui);
+    if let Some(ts) = handle_custom_func(field, place, &name_str, mutable, attr) {
+        return ts;
+    }
+
+    if let Some(ts) = handle_color(field, place, &name_str, mutable, attr) {
+        return ts;
+    }
+
+    if let Some(ts) = handle_inline(field, place, &name_str, mutable, attr) {
+        return ts;
+    }
+
+    if let Some(ts) = internal_paths::try_handle_internal_path(field, place, &name_str, mutable, attr) {
+        return ts;
+    }
+
+    utils::get_default_function_call(field, place, &name_str, mutable)
+}
+
+fn handle_color(
+    field: &Field,
+    place: &TokenStream,
+    name_str: &str,
+    mutable: bool,
+    attrs: &AttributeArgs,
+) -> Option<TokenStream> {
+    let color = attrs.color.as_ref()?;
+    let space = if color.linear {
+        quote!(egui_inspect::ColorSpace::Linear)
+    } else {
+        quote!(egui_inspect::ColorSpace::Srgb)
+    };
+    let alpha = color.alpha;
+    let options = quote!(egui_inspect::ColorOptions { space: #space, alpha: #alpha });
+
+    Some(if mutable {
+        quote_spanned! { field.span() => {
+                egui_inspect::InspectColor::inspect_mut_color(&mut #place, &#name_str, ui, #options);
             }
-        });
+        }
+    } else {
+        quote_spanned! { field.span() => {
+                egui_inspect::InspectColor::inspect_color(&#place, &#name_str, ui, #options);
+            }
+        }
+    })
+}
+
+fn handle_inline(
+    field: &Field,
+    place: &TokenStream,
+    name_str: &str,
+    mutable: bool,
+    attrs: &AttributeArgs,
+) -> Option<TokenStream> {
+    if !attrs.inline {
+        return None;
     }
 
-    if (!mutable || (mutable && attrs.no_edit)) && attrs.custom_func.is_some() {
-        let custom_func = attrs.custom_func.as_ref().unwrap();
-        let ident = syn::Path::from_string(custom_func)
-            .expect(format!("Could not find function: {}", custom_func).as_str());
-        return Some(quote_spanned! { field.span() => {
-                #ident(&self.#name, &#name_str, ui);
+    Some(if mutable {
+        quote_spanned! { field.span() => {
+                egui_inspect::InspectInline::inspect_mut_inline(&mut #place, &#name_str, ui);
             }
-        });
+        }
+    } else {
+        quote_spanned! { field.span() => {
+                egui_inspect::InspectInline::inspect_inline(&#place, &#name_str, ui);
+            }
+        }
+    })
+}
+
+fn handle_custom_func(
+    field: &Field,
+    place: &TokenStream,
+    name_str: &str,
+    mutable: bool,
+    attrs: &AttributeArgs,
+) -> Option<TokenStream> {
+    if mutable && !attrs.no_edit {
+        if let Some(custom_func_mut) = &attrs.custom_func_mut {
+            let ident = syn::Path::from_string(custom_func_mut)
+                .unwrap_or_else(|_| panic!("Could not find function: {}", custom_func_mut));
+            return Some(quote_spanned! { field.span() => {
+                    #ident(&mut #place, &#name_str, ui);
+                }
+            });
+        }
+    }
+
+    if !mutable || attrs.no_edit {
+        if let Some(custom_func) = &attrs.custom_func {
+            let ident = syn::Path::from_string(custom_func)
+                .unwrap_or_else(|_| panic!("Could not find function: {}", custom_func));
+            return Some(quote_spanned! { field.span() => {
+                    #ident(&#place, &#name_str, ui);
+                }
+            });
+        }
     }
 
-    return None;
+    None
 }
@@ -1,38 +1,64 @@
-use proc_macro2::TokenStream;
-use quote::quote_spanned;
+use proc_macro2::{Literal, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::Type::{Path, Reference};
-use syn::{Field, Type};
-
-use crate::AttributeArgs;
+use syn::{Expr, ExprLit, ExprUnary, Field, Lit, Type, UnOp};
 
 pub fn get_path_str(type_path: &Type) -> Option<String> {
     match type_path {
         Path(type_path) => {
-            let ident = type_path
+            let segment = type_path
                 .path
-                .get_ident();
-            if let Some(name) = ident {
-                return Some(name.to_string());
-            }
-            return None;
+                .segments
+                .last();
+            segment.map(|segment| segment.ident.to_string())
         }
-        Reference(type_ref) => get_path_str(&*type_ref.elem),
+        Reference(type_ref) => get_path_str(&type_ref.elem),
         _ => Some("".to_string()),
     }
 }
 
-pub(crate) fn get_default_function_call(field: &Field, mutable: bool, attrs: &AttributeArgs) -> TokenStream {
-    let name = &field.ident;
+/// Turn an optional attribute value into an `Option` expression
+pub(crate) fn quote_option<T: ToTokens>(value: &Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(#value)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+/// Make a literal bound match the number type `path_str`, so that `min = 12.0` can be used
+/// on integers and `max = 100` on floats. Other expressions are used as is.
+pub(crate) fn typed_number_bound(bound: &Expr, path_str: &str) -> TokenStream {
+    let is_float = path_str == "f32" || path_str == "f64";
 
-    let name_str = match &attrs.name {
-        Some(n) => n.clone(),
-        None => name.clone().unwrap().to_string(),
-    };
+    match bound {
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => {
+            let expr = typed_number_bound(expr, path_str);
+            quote_spanned!(bound.span() => -#expr)
+        }
+        Expr::Lit(ExprLit { lit: Lit::Float(lit), .. }) if !is_float => {
+            let value: f64 = lit.base10_parse().expect("Could not parse float literal");
+            if value.fract() != 0.0 {
+                panic!("Bound {} can't be used on an integer", value);
+            }
+            let mut literal = Literal::i128_unsuffixed(value as i128);
+            literal.set_span(lit.span());
+            quote!(#literal)
+        }
+        Expr::Lit(ExprLit { lit: Lit::Int(lit), .. }) if is_float && lit.suffix().is_empty() => {
+            let value: f64 = lit.base10_parse().expect("Could not parse integer literal");
+            let mut literal = Literal::f64_unsuffixed(value);
+            literal.set_span(lit.span());
+            quote!(#literal)
+        }
+        _ => quote!(#bound),
+    }
+}
 
-    return if mutable {
-        quote_spanned! {field.span() => {egui_inspect::EguiInspect::inspect_mut(&mut self.#name, &#name_str, ui);}}
+pub(crate) fn get_default_function_call(field: &Field, place: &TokenStream, name_str: &str, mutable: bool) -> TokenStream {
+    if mutable {
+        quote_spanned! {field.span() => {egui_inspect::EguiInspect::inspect_mut(&mut #place, &#name_str, ui);}}
     } else {
-        quote_spanned! {field.span() => {egui_inspect::EguiInspect::inspect(&self.#name, &#name_str, ui);}}
-    };
+        quote_spanned! {field.span() => {egui_inspect::EguiInspect::inspect(&#place, &#name_str, ui);}}
+    }
 }